@@ -1,7 +1,6 @@
-use core::marker::Tuple;
 use core::ops::*;
 
-pub trait Constructor<T: Tuple + Copy> {
+pub trait Constructor<T> {
     fn new(para: T) -> Self;
 }
 
@@ -12,20 +11,182 @@ pub trait Components<const N: usize>: Copy {
     fn components(self) -> [Self::Comp; N];
 }
 
-impl Components<1> for f64 {
-    type Comp = f64;
+// A scalar or vector passed to a GLSL-style constructor, which consumes the
+// components of all its arguments in order.
+pub trait Argument<T> {
+    const LEN: usize;
 
-    fn from_components(comps: [Self::Comp; 1]) -> Self {
-        comps[0]
-    }
-    fn components(self) -> [Self::Comp; 1] {
-        [self]
-    }
+    fn flatten(self) -> impl Iterator<Item = T>;
+}
+
+// Types whose component-wise comparisons produce a boolean vector of the same
+// length, e.g. `lessThan(vec3, vec3) -> bvec3`.
+pub trait Relational<const N: usize>: Components<N> {
+    type Bool: Components<N, Comp = bool>;
+}
+
+// Column vector times row vector, producing a matrix with the row's length as
+// its column count and the column's length as its row count.
+pub trait OuterProduct<Row> {
+    type Output;
+
+    fn outer_product(self, row: Row) -> Self::Output;
+}
+
+pub trait Transpose {
+    type Output;
+
+    fn transpose(self) -> Self::Output;
+}
+
+pub trait Inverse: Copy {
+    type Scalar: Float;
+
+    fn determinant(self) -> Self::Scalar;
+    fn inverse(self) -> Self;
+
+    // `None` when the inverse does not fit the scalar type, i.e. for singular
+    // matrices and for determinants so small that `1 / det` overflows.
+    fn try_inverse(self) -> Option<Self>;
+}
+
+pub trait Float:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Neg<Output = Self>
+    + AddAssign
+    + SubAssign
+    + MulAssign
+    + DivAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn sqrt(self) -> Self;
+    fn abs(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+
+    fn to_radians(self) -> Self;
+    fn to_degrees(self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn tan(self) -> Self;
+    fn asin(self) -> Self;
+    fn acos(self) -> Self;
+    fn atan(self) -> Self;
+    fn atan2(self, rhs: Self) -> Self;
+    fn sinh(self) -> Self;
+    fn cosh(self) -> Self;
+    fn tanh(self) -> Self;
+    fn asinh(self) -> Self;
+    fn acosh(self) -> Self;
+    fn atanh(self) -> Self;
+
+    fn powf(self, rhs: Self) -> Self;
+    fn exp(self) -> Self;
+    fn ln(self) -> Self;
+    fn exp2(self) -> Self;
+    fn log2(self) -> Self;
+}
+
+macro_rules! forward {
+    ($ty:ty; $($method:ident($($para:ident),*);)*) => {
+        $(
+            fn $method(self, $($para: Self),*) -> Self { <$ty>::$method(self, $($para),*) }
+        )*
+    };
+}
+
+macro_rules! float_impl {
+    ($($ty:ty),*) => {
+        $(
+            impl Float for $ty {
+                const ZERO: Self = 0.0;
+                const ONE: Self = 1.0;
+
+                forward! {
+                    $ty;
+
+                    sqrt();
+                    abs();
+                    floor();
+                    ceil();
+
+                    to_radians();
+                    to_degrees();
+                    sin();
+                    cos();
+                    tan();
+                    asin();
+                    acos();
+                    atan();
+                    atan2(rhs);
+                    sinh();
+                    cosh();
+                    tanh();
+                    asinh();
+                    acosh();
+                    atanh();
+
+                    powf(rhs);
+                    exp();
+                    ln();
+                    exp2();
+                    log2();
+                }
+            }
+        )*
+    };
+}
+
+float_impl!(f32, f64);
+
+macro_rules! scalar_impl {
+    ($($ty:ty),*) => {
+        $(
+            impl Constructor<($ty,)> for $ty {
+                fn new((single,): ($ty,)) -> Self {
+                    single
+                }
+            }
+
+            impl Components<1> for $ty {
+                type Comp = $ty;
+
+                fn from_components(comps: [Self::Comp; 1]) -> Self {
+                    comps[0]
+                }
+                fn components(self) -> [Self::Comp; 1] {
+                    [self]
+                }
+            }
+
+            impl Relational<1> for $ty {
+                type Bool = bool;
+            }
+
+            impl Argument<$ty> for $ty {
+                const LEN: usize = 1;
+
+                fn flatten(self) -> impl Iterator<Item = $ty> {
+                    core::iter::once(self)
+                }
+            }
+        )*
+    };
 }
 
+scalar_impl!(f32, f64, i32, u32, bool);
+
 macro_rules! define {
     {
         $self:ident: $n:literal * $ty:ty;
+        $(#[$meta:meta])*
         $name:ident($($field:ident),*);
 
         $(
@@ -40,12 +201,6 @@ macro_rules! define {
             }
         )?
 
-        $(
-            swizzle {
-                $($swizzle_name:ident($($swizzle_field:ident),*) -> $swizzle_ty:ty;)*
-            }
-        )?
-
         $(
             op {
                 $($op_ty:ty: $({$($op_additional:item)*})? $op_fn:ident($($op_para_name:ident: $op_para_ty:ty),*) -> $op_ret:ty => $op_value:expr;)*
@@ -61,22 +216,19 @@ macro_rules! define {
         // ($(define!(discard $field -> $ty),)*) expands to a tuple of $ty with $n length
 
         #[derive(Debug, Clone, Copy, PartialEq)]
+        $(#[$meta])*
         #[repr(C)]
         pub struct $name {
             $(pub $field: $ty),*
         }
 
-        pub fn $name<T: Tuple + Copy>(para: T) -> $name where $name: Constructor<T> { $name::new(para) }
+        pub fn $name<T>(para: T) -> $name where $name: Constructor<T> { $name::new(para) }
 
         impl $name {
             $($(
                 pub fn $acc_name($self) -> $acc_ty { $acc_value }
             )*)?
 
-            $($(
-                pub fn $swizzle_name(self) -> $swizzle_ty { <$swizzle_ty>::new(($(self.$swizzle_field,)*)) }
-            )*)?
-
             $($($assoc)*)?
         }
 
@@ -87,27 +239,86 @@ macro_rules! define {
             fn components(self) -> [Self::Comp; $n] { [$(self.$field),*] }
         }
 
-        impl Deref for $name {
-            type Target = ($(define!(discard $field -> $ty),)*);
+        impl $name {
+            pub fn as_array(&self) -> &[$ty; $n] {
+                // Unsafe: #[repr(C)] with $n fields of one type has the layout of [$ty; $n]
+                unsafe { &*(self as *const Self as *const [$ty; $n]) }
+            }
+
+            pub fn as_mut_array(&mut self) -> &mut [$ty; $n] {
+                // Unsafe: #[repr(C)] with $n fields of one type has the layout of [$ty; $n]
+                unsafe { &mut *(self as *mut Self as *mut [$ty; $n]) }
+            }
+        }
+
+        impl $name {
+            pub fn iter(&self) -> core::slice::Iter<'_, $ty> {
+                self.as_array().iter()
+            }
+
+            pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, $ty> {
+                self.as_mut_array().iter_mut()
+            }
+        }
+
+        impl Index<usize> for $name {
+            type Output = $ty;
 
-            fn deref(&self) -> &Self::Target { unsafe { &*(self as *const Self as *const Self::Target) } }
+            fn index(&self, index: usize) -> &$ty { &self.as_array()[index] }
         }
 
-        impl DerefMut for $name {
-            fn deref_mut(&mut self) -> &mut Self::Target { unsafe { &mut*(self as *mut Self as *mut Self::Target) } }
+        impl IndexMut<usize> for $name {
+            fn index_mut(&mut self, index: usize) -> &mut $ty { &mut self.as_mut_array()[index] }
         }
 
-        impl Constructor<($name,)> for $name {
-            fn new(($name,): ($name,)) -> Self {
-                $name
+        impl IntoIterator for $name {
+            type Item = $ty;
+            type IntoIter = core::array::IntoIter<$ty, $n>;
+
+            fn into_iter(self) -> Self::IntoIter { self.components().into_iter() }
+        }
+
+        impl<'a> IntoIterator for &'a $name {
+            type Item = &'a $ty;
+            type IntoIter = core::slice::Iter<'a, $ty>;
+
+            fn into_iter(self) -> Self::IntoIter { self.iter() }
+        }
+
+        impl<'a> IntoIterator for &'a mut $name {
+            type Item = &'a mut $ty;
+            type IntoIter = core::slice::IterMut<'a, $ty>;
+
+            fn into_iter(self) -> Self::IntoIter { self.iter_mut() }
+        }
+
+        impl From<[$ty; $n]> for $name {
+            fn from(array: [$ty; $n]) -> Self {
+                Self::from_components(array)
             }
         }
 
-        impl Constructor<($(define!(discard $field -> $ty),)*)> for $name {
-            fn new(($($field,)*): ($(define!(discard $field -> $ty),)*)) -> Self {
-                Self {
-                    $($field,)*
-                }
+        impl From<$name> for [$ty; $n] {
+            fn from(value: $name) -> Self {
+                value.components()
+            }
+        }
+
+        impl From<($(define!(discard $field -> $ty),)*)> for $name {
+            fn from(tuple: ($(define!(discard $field -> $ty),)*)) -> Self {
+                Self::new(tuple)
+            }
+        }
+
+        impl From<$name> for ($(define!(discard $field -> $ty),)*) {
+            fn from($name { $($field),* }: $name) -> Self {
+                ($($field,)*)
+            }
+        }
+
+        impl Constructor<($name,)> for $name {
+            fn new(($name,): ($name,)) -> Self {
+                $name
             }
         }
 
@@ -148,46 +359,99 @@ macro_rules! num_impl {
             Add: {
                 type Output = $name;
             } add(self: $name, rhs: $name) -> $name => $name(($(self.$field + rhs.$field,)*));
+            Add<$ty>: {
+                type Output = $name;
+            } add(self: $name, rhs: $ty) -> $name => $name(($(self.$field + rhs,)*));
             AddAssign: add_assign(self: &mut $name, rhs: $name) -> () => {
                 $(self.$field += rhs.$field;)*;
             };
+            AddAssign<$ty>: add_assign(self: &mut $name, rhs: $ty) -> () => {
+                $(self.$field += rhs;)*;
+            };
 
             Sub: {
                 type Output = $name;
             } sub(self: $name, rhs: $name) -> $name => $name(($(self.$field - rhs.$field,)*));
+            Sub<$ty>: {
+                type Output = $name;
+            } sub(self: $name, rhs: $ty) -> $name => $name(($(self.$field - rhs,)*));
             SubAssign: sub_assign(self: &mut $name, rhs: $name) -> () => {
                 $(self.$field -= rhs.$field;)*;
             };
+            SubAssign<$ty>: sub_assign(self: &mut $name, rhs: $ty) -> () => {
+                $(self.$field -= rhs;)*;
+            };
 
             Mul: {
                 type Output = $name;
             } mul(self: $name, rhs: $name) -> $name => $name(($(self.$field * rhs.$field,)*));
-            Mul<f64>: {
+            Mul<$ty>: {
                 type Output = $name;
-            } mul(self: $name, rhs: f64) -> $name => $name(($(self.$field * rhs,)*));
+            } mul(self: $name, rhs: $ty) -> $name => $name(($(self.$field * rhs,)*));
             MulAssign: mul_assign(self: &mut $name, rhs: $name) -> () => {
                 $(self.$field *= rhs.$field;)*;
             };
-            MulAssign<f64>: mul_assign(self: &mut $name, rhs: f64) -> () => {
+            MulAssign<$ty>: mul_assign(self: &mut $name, rhs: $ty) -> () => {
                 $(self.$field *= rhs;)*;
             };
 
             Div: {
                 type Output = $name;
             } div(self: $name, rhs: $name) -> $name => $name(($(self.$field / rhs.$field,)*));
-            Div<f64>: {
+            Div<$ty>: {
                 type Output = $name;
-            } div(self: $name, rhs: f64) -> $name => $name(($(self.$field / rhs,)*));
+            } div(self: $name, rhs: $ty) -> $name => $name(($(self.$field / rhs,)*));
             DivAssign: div_assign(self: &mut $name, rhs: $name) -> () => {
                 $(self.$field /= rhs.$field;)*;
             };
-            DivAssign<f64>: div_assign(self: &mut $name, rhs: f64) -> () => {
+            DivAssign<$ty>: div_assign(self: &mut $name, rhs: $ty) -> () => {
                 $(self.$field /= rhs;)*;
             };
         }
+
+        num_impl! {
+            impl($ty);
+
+            Add<$name>: {
+                type Output = $name;
+            } add(self: $ty, rhs: $name) -> $name => $name(($(self + rhs.$field,)*));
+            Sub<$name>: {
+                type Output = $name;
+            } sub(self: $ty, rhs: $name) -> $name => $name(($(self - rhs.$field,)*));
+            Mul<$name>: {
+                type Output = $name;
+            } mul(self: $ty, rhs: $name) -> $name => $name(($(self * rhs.$field,)*));
+            Div<$name>: {
+                type Output = $name;
+            } div(self: $ty, rhs: $name) -> $name => $name(($(self / rhs.$field,)*));
+        }
+
+        impl core::iter::Sum for $name {
+            fn sum<I: Iterator<Item = $name>>(iter: I) -> $name {
+                iter.fold($name((0 as $ty,)), Add::add)
+            }
+        }
+
+        impl<'a> core::iter::Sum<&'a $name> for $name {
+            fn sum<I: Iterator<Item = &'a $name>>(iter: I) -> $name {
+                iter.copied().sum()
+            }
+        }
+
+        impl core::iter::Product for $name {
+            fn product<I: Iterator<Item = $name>>(iter: I) -> $name {
+                iter.fold($name((1 as $ty,)), Mul::mul)
+            }
+        }
+
+        impl<'a> core::iter::Product<&'a $name> for $name {
+            fn product<I: Iterator<Item = &'a $name>>(iter: I) -> $name {
+                iter.copied().product()
+            }
+        }
     };
     {
-        impl($name:ident);
+        impl($name:ty);
 
         $($op_ty:ty: $({$($op_additional:item)*})? $op_fn:ident($($op_para_name:ident: $op_para_ty:ty),*) -> $op_ret:ty => $op_value:expr;)*
     } => {
@@ -202,556 +466,1254 @@ macro_rules! num_impl {
     }
 }
 
-define! {
-    self: 2 * f64;
-    vec2(x, y);
+// Operators shared by every matrix shape. `$col_vec` is a column of the matrix
+// and `$row_vec` a row, so a matrix with C columns and R rows maps `vecC` to `vecR`.
+macro_rules! mat_impl {
+    ($ty:ty: $name:ident($($col:ident),*): $col_vec:ident * $row_vec:ident($($field:ident),*)) => {
+        impl Constructor<($ty,)> for $name {
+            fn new((single,): ($ty,)) -> Self {
+                Self::from_components(core::array::from_fn(|c| {
+                    core::array::from_fn(|r| if c == r { single } else { 0.0 })
+                }))
+            }
+        }
 
-    constructor {
-        (single: f64) => vec2((single, single));
-    }
+        impl $name {
+            // GLSL's `m[i]`. `Index` can only hand out the stored `[$ty; R]` column,
+            // as it has to return a reference into the matrix.
+            pub fn col(self, index: usize) -> $col_vec {
+                $col_vec((self[index],))
+            }
+        }
 
-    swizzle {
-        xx(x, x) -> vec2;
-        xy(x, y) -> vec2;
-        yx(y, x) -> vec2;
-        yy(y, y) -> vec2;
-
-        xxx(x, x, x) -> vec3;
-        xxy(x, x, y) -> vec3;
-        xyx(x, y, x) -> vec3;
-        xyy(x, y, y) -> vec3;
-        yxx(y, x, x) -> vec3;
-        yxy(y, x, y) -> vec3;
-        yyx(y, y, x) -> vec3;
-        yyy(y, y, y) -> vec3;
-
-        xxxx(x, x, x, x) -> vec4;
-        xxxy(x, x, x, y) -> vec4;
-        xxyx(x, x, y, x) -> vec4;
-        xxyy(x, x, y, y) -> vec4;
-        xyxx(x, y, x, x) -> vec4;
-        xyxy(x, y, x, y) -> vec4;
-        xyyx(x, y, y, x) -> vec4;
-        xyyy(x, y, y, y) -> vec4;
-        yxxx(y, x, x, x) -> vec4;
-        yxxy(y, x, x, y) -> vec4;
-        yxyx(y, x, y, x) -> vec4;
-        yxyy(y, x, y, y) -> vec4;
-        yyxx(y, y, x, x) -> vec4;
-        yyxy(y, y, x, y) -> vec4;
-        yyyx(y, y, y, x) -> vec4;
-        yyyy(y, y, y, y) -> vec4;
-    }
+        num_impl! {
+            impl($name);
+
+            Add: {
+                type Output = $name;
+            } add(self: $name, rhs: $name) -> $name => $name(($(($col_vec((self.$col,)) + $col_vec((rhs.$col,))).components(),)*));
+            AddAssign: add_assign(self: &mut $name, rhs: $name) -> () => {
+                *self = *self + rhs;
+            };
+
+            Sub: {
+                type Output = $name;
+            } sub(self: $name, rhs: $name) -> $name => $name(($(($col_vec((self.$col,)) - $col_vec((rhs.$col,))).components(),)*));
+            SubAssign: sub_assign(self: &mut $name, rhs: $name) -> () => {
+                *self = *self - rhs;
+            };
+
+            Mul<$row_vec>: {
+                type Output = $col_vec;
+            } mul(self: $name, vec: $row_vec) -> $col_vec => $col_vec((0.0,)) $(+ $col_vec((self.$col,)) * vec.$field)*;
+            Mul<$ty>: {
+                type Output = $name;
+            } mul(self: $name, rhs: $ty) -> $name => $name(($(($col_vec((self.$col,)) * rhs).components(),)*));
+            MulAssign<$ty>: mul_assign(self: &mut $name, rhs: $ty) -> () => {
+                *self = *self * rhs;
+            };
+
+            Div<$ty>: {
+                type Output = $name;
+            } div(self: $name, rhs: $ty) -> $name => $name(($(($col_vec((self.$col,)) / rhs).components(),)*));
+            DivAssign<$ty>: div_assign(self: &mut $name, rhs: $ty) -> () => {
+                *self = *self / rhs;
+            };
+        }
+
+        // row vector times matrix, i.e. `transpose(mat) * vec`
+        num_impl! {
+            impl($col_vec);
+
+            Mul<$name>: {
+                type Output = $row_vec;
+            } mul(self: $col_vec, mat: $name) -> $row_vec => $row_vec(($(($col_vec((mat.$col,)) * self).into_iter().sum::<$ty>(),)*));
+        }
+
+        num_impl! {
+            impl($ty);
+
+            Mul<$name>: {
+                type Output = $name;
+            } mul(self: $ty, mat: $name) -> $name => mat * self;
+        }
+    };
 }
 
-num_impl!(2 * f64: vec2(x, y));
+// In-place products, which only keep their type for square matrices.
+macro_rules! square_impl {
+    ($name:ident: $vec:ident) => {
+        num_impl! {
+            impl($name);
 
-define! {
-    self: 3 * f64;
-    vec3(x, y, z);
+            MulAssign: mul_assign(self: &mut $name, rhs: $name) -> () => {
+                *self = *self * rhs;
+            };
+        }
 
-    constructor {
-        (single: f64) => vec3((single, single, single));
-        (vec2: vec2, z: f64) => vec3((vec2.x, vec2.y, z));
-    }
+        num_impl! {
+            impl($vec);
 
-    swizzle {
-        xx(x, x) -> vec2;
-        xy(x, y) -> vec2;
-        xz(x, z) -> vec2;
-        yx(y, x) -> vec2;
-        yy(y, y) -> vec2;
-        yz(y, z) -> vec2;
-        zx(z, x) -> vec2;
-        zy(z, y) -> vec2;
-        zz(z, z) -> vec2;
-
-        xxx(x, x, x) -> vec3;
-        xxy(x, x, y) -> vec3;
-        xxz(x, x, z) -> vec3;
-        xyx(x, y, x) -> vec3;
-        xyy(x, y, y) -> vec3;
-        xyz(x, y, z) -> vec3;
-        xzx(x, z, x) -> vec3;
-        xzy(x, z, y) -> vec3;
-        xzz(x, z, z) -> vec3;
-        yxx(y, x, x) -> vec3;
-        yxy(y, x, y) -> vec3;
-        yxz(y, x, z) -> vec3;
-        yyx(y, y, x) -> vec3;
-        yyy(y, y, y) -> vec3;
-        yyz(y, y, z) -> vec3;
-        yzx(y, z, x) -> vec3;
-        yzy(y, z, y) -> vec3;
-        yzz(y, z, z) -> vec3;
-        zxx(z, x, x) -> vec3;
-        zxy(z, x, y) -> vec3;
-        zxz(z, x, z) -> vec3;
-        zyx(z, y, x) -> vec3;
-        zyy(z, y, y) -> vec3;
-        zyz(z, y, z) -> vec3;
-        zzx(z, z, x) -> vec3;
-        zzy(z, z, y) -> vec3;
-        zzz(z, z, z) -> vec3;
-
-        xxxx(x, x, x, x) -> vec4;
-        xxxy(x, x, x, y) -> vec4;
-        xxxz(x, x, x, z) -> vec4;
-        xxyx(x, x, y, x) -> vec4;
-        xxyy(x, x, y, y) -> vec4;
-        xxyz(x, x, y, z) -> vec4;
-        xxzx(x, x, z, x) -> vec4;
-        xxzy(x, x, z, y) -> vec4;
-        xxzz(x, x, z, z) -> vec4;
-        xyxx(x, y, x, x) -> vec4;
-        xyxy(x, y, x, y) -> vec4;
-        xyxz(x, y, x, z) -> vec4;
-        xyyx(x, y, y, x) -> vec4;
-        xyyy(x, y, y, y) -> vec4;
-        xyyz(x, y, y, z) -> vec4;
-        xyzx(x, y, z, x) -> vec4;
-        xyzy(x, y, z, y) -> vec4;
-        xyzz(x, y, z, z) -> vec4;
-        xzxx(x, z, x, x) -> vec4;
-        xzxy(x, z, x, y) -> vec4;
-        xzxz(x, z, x, z) -> vec4;
-        xzyx(x, z, y, x) -> vec4;
-        xzyy(x, z, y, y) -> vec4;
-        xzyz(x, z, y, z) -> vec4;
-        xzzx(x, z, z, x) -> vec4;
-        xzzy(x, z, z, y) -> vec4;
-        xzzz(x, z, z, z) -> vec4;
-        yxxx(y, x, x, x) -> vec4;
-        yxxy(y, x, x, y) -> vec4;
-        yxxz(y, x, x, z) -> vec4;
-        yxyx(y, x, y, x) -> vec4;
-        yxyy(y, x, y, y) -> vec4;
-        yxyz(y, x, y, z) -> vec4;
-        yxzx(y, x, z, x) -> vec4;
-        yxzy(y, x, z, y) -> vec4;
-        yxzz(y, x, z, z) -> vec4;
-        yyxx(y, y, x, x) -> vec4;
-        yyxy(y, y, x, y) -> vec4;
-        yyxz(y, y, x, z) -> vec4;
-        yyyx(y, y, y, x) -> vec4;
-        yyyy(y, y, y, y) -> vec4;
-        yyyz(y, y, y, z) -> vec4;
-        yyzx(y, y, z, x) -> vec4;
-        yyzy(y, y, z, y) -> vec4;
-        yyzz(y, y, z, z) -> vec4;
-        yzxx(y, z, x, x) -> vec4;
-        yzxy(y, z, x, y) -> vec4;
-        yzxz(y, z, x, z) -> vec4;
-        yzyx(y, z, y, x) -> vec4;
-        yzyy(y, z, y, y) -> vec4;
-        yzyz(y, z, y, z) -> vec4;
-        yzzx(y, z, z, x) -> vec4;
-        yzzy(y, z, z, y) -> vec4;
-        yzzz(y, z, z, z) -> vec4;
-        zxxx(z, x, x, x) -> vec4;
-        zxxy(z, x, x, y) -> vec4;
-        zxxz(z, x, x, z) -> vec4;
-        zxyx(z, x, y, x) -> vec4;
-        zxyy(z, x, y, y) -> vec4;
-        zxyz(z, x, y, z) -> vec4;
-        zxzx(z, x, z, x) -> vec4;
-        zxzy(z, x, z, y) -> vec4;
-        zxzz(z, x, z, z) -> vec4;
-        zyxx(z, y, x, x) -> vec4;
-        zyxy(z, y, x, y) -> vec4;
-        zyxz(z, y, x, z) -> vec4;
-        zyyx(z, y, y, x) -> vec4;
-        zyyy(z, y, y, y) -> vec4;
-        zyyz(z, y, y, z) -> vec4;
-        zyzx(z, y, z, x) -> vec4;
-        zyzy(z, y, z, y) -> vec4;
-        zyzz(z, y, z, z) -> vec4;
-        zzxx(z, z, x, x) -> vec4;
-        zzxy(z, z, x, y) -> vec4;
-        zzxz(z, z, x, z) -> vec4;
-        zzyx(z, z, y, x) -> vec4;
-        zzyy(z, z, y, y) -> vec4;
-        zzyz(z, z, y, z) -> vec4;
-        zzzx(z, z, z, x) -> vec4;
-        zzzy(z, z, z, y) -> vec4;
-        zzzz(z, z, z, z) -> vec4;
-    }
+            MulAssign<$name>: mul_assign(self: &mut $vec, mat: $name) -> () => {
+                *self = *self * mat;
+            };
+        }
+    };
 }
 
-num_impl!(3 * f64: vec3(x, y, z));
+// Matrix products, listed per left operand as `lhs: row_vec * (rhs -> out, ...)`
+// so that only products with matching inner dimensions exist.
+macro_rules! mat_mul {
+    ($($lhs:ident: $row_vec:ident * ($($rhs:ident -> $out:ident),*);)*) => {
+        $($(
+            impl Mul<$rhs> for $lhs {
+                type Output = $out;
+
+                fn mul(self, rhs: $rhs) -> $out {
+                    $out::from_components(rhs.components().map(|col| (self * $row_vec::from_components(col)).components()))
+                }
+            }
+        )*)*
+    };
+}
 
-define! {
-    self: 4 * f64;
-    vec4(x, y, z, w);
+// Swaps rows and columns, so column `c` of the result is row `c` of `self`.
+macro_rules! transpose_impl {
+    ($($name:ident -> $out:ident),*) => {
+        $(
+            impl Transpose for $name {
+                type Output = $out;
 
-    constructor {
-        (single: f64) => vec4((single, single, single, single));
-        (vec2: vec2, z: f64, w: f64) => vec4((vec2.x, vec2.y, z, w));
-        (vec3: vec3, w: f64) => vec4((vec3.x, vec3.y, vec3.z, w));
-    }
+                fn transpose(self) -> $out {
+                    let cols = self.components();
 
-    swizzle {
-        xx(x, x) -> vec2;
-        xy(x, y) -> vec2;
-        xz(x, z) -> vec2;
-        xw(x, w) -> vec2;
-        yx(y, x) -> vec2;
-        yy(y, y) -> vec2;
-        yz(y, z) -> vec2;
-        yw(y, w) -> vec2;
-        zx(z, x) -> vec2;
-        zy(z, y) -> vec2;
-        zz(z, z) -> vec2;
-        zw(z, w) -> vec2;
-        wx(w, x) -> vec2;
-        wy(w, y) -> vec2;
-        wz(w, z) -> vec2;
-        ww(w, w) -> vec2;
-
-        xxx(x, x, x) -> vec3;
-        xxy(x, x, y) -> vec3;
-        xxz(x, x, z) -> vec3;
-        xxw(x, x, w) -> vec3;
-        xyx(x, y, x) -> vec3;
-        xyy(x, y, y) -> vec3;
-        xyz(x, y, z) -> vec3;
-        xyw(x, y, w) -> vec3;
-        xzx(x, z, x) -> vec3;
-        xzy(x, z, y) -> vec3;
-        xzz(x, z, z) -> vec3;
-        xzw(x, z, w) -> vec3;
-        xwx(x, w, x) -> vec3;
-        xwy(x, w, y) -> vec3;
-        xwz(x, w, z) -> vec3;
-        xww(x, w, w) -> vec3;
-        yxx(y, x, x) -> vec3;
-        yxy(y, x, y) -> vec3;
-        yxz(y, x, z) -> vec3;
-        yxw(y, x, w) -> vec3;
-        yyx(y, y, x) -> vec3;
-        yyy(y, y, y) -> vec3;
-        yyz(y, y, z) -> vec3;
-        yyw(y, y, w) -> vec3;
-        yzx(y, z, x) -> vec3;
-        yzy(y, z, y) -> vec3;
-        yzz(y, z, z) -> vec3;
-        yzw(y, z, w) -> vec3;
-        ywx(y, w, x) -> vec3;
-        ywy(y, w, y) -> vec3;
-        ywz(y, w, z) -> vec3;
-        yww(y, w, w) -> vec3;
-        zxx(z, x, x) -> vec3;
-        zxy(z, x, y) -> vec3;
-        zxz(z, x, z) -> vec3;
-        zxw(z, x, w) -> vec3;
-        zyx(z, y, x) -> vec3;
-        zyy(z, y, y) -> vec3;
-        zyz(z, y, z) -> vec3;
-        zyw(z, y, w) -> vec3;
-        zzx(z, z, x) -> vec3;
-        zzy(z, z, y) -> vec3;
-        zzz(z, z, z) -> vec3;
-        zzw(z, z, w) -> vec3;
-        zwx(z, w, x) -> vec3;
-        zwy(z, w, y) -> vec3;
-        zwz(z, w, z) -> vec3;
-        zww(z, w, w) -> vec3;
-        wxx(w, x, x) -> vec3;
-        wxy(w, x, y) -> vec3;
-        wxz(w, x, z) -> vec3;
-        wxw(w, x, w) -> vec3;
-        wyx(w, y, x) -> vec3;
-        wyy(w, y, y) -> vec3;
-        wyz(w, y, z) -> vec3;
-        wyw(w, y, w) -> vec3;
-        wzx(w, z, x) -> vec3;
-        wzy(w, z, y) -> vec3;
-        wzz(w, z, z) -> vec3;
-        wzw(w, z, w) -> vec3;
-        wwx(w, w, x) -> vec3;
-        wwy(w, w, y) -> vec3;
-        wwz(w, w, z) -> vec3;
-        www(w, w, w) -> vec3;
-
-        xxxx(x, x, x, x) -> vec4;
-        xxxy(x, x, x, y) -> vec4;
-        xxxz(x, x, x, z) -> vec4;
-        xxxw(x, x, x, w) -> vec4;
-        xxyx(x, x, y, x) -> vec4;
-        xxyy(x, x, y, y) -> vec4;
-        xxyz(x, x, y, z) -> vec4;
-        xxyw(x, x, y, w) -> vec4;
-        xxzx(x, x, z, x) -> vec4;
-        xxzy(x, x, z, y) -> vec4;
-        xxzz(x, x, z, z) -> vec4;
-        xxzw(x, x, z, w) -> vec4;
-        xxwx(x, x, w, x) -> vec4;
-        xxwy(x, x, w, y) -> vec4;
-        xxwz(x, x, w, z) -> vec4;
-        xxww(x, x, w, w) -> vec4;
-        xyxx(x, y, x, x) -> vec4;
-        xyxy(x, y, x, y) -> vec4;
-        xyxz(x, y, x, z) -> vec4;
-        xyxw(x, y, x, w) -> vec4;
-        xyyx(x, y, y, x) -> vec4;
-        xyyy(x, y, y, y) -> vec4;
-        xyyz(x, y, y, z) -> vec4;
-        xyyw(x, y, y, w) -> vec4;
-        xyzx(x, y, z, x) -> vec4;
-        xyzy(x, y, z, y) -> vec4;
-        xyzz(x, y, z, z) -> vec4;
-        xyzw(x, y, z, w) -> vec4;
-        xywx(x, y, w, x) -> vec4;
-        xywy(x, y, w, y) -> vec4;
-        xywz(x, y, w, z) -> vec4;
-        xyww(x, y, w, w) -> vec4;
-        xzxx(x, z, x, x) -> vec4;
-        xzxy(x, z, x, y) -> vec4;
-        xzxz(x, z, x, z) -> vec4;
-        xzxw(x, z, x, w) -> vec4;
-        xzyx(x, z, y, x) -> vec4;
-        xzyy(x, z, y, y) -> vec4;
-        xzyz(x, z, y, z) -> vec4;
-        xzyw(x, z, y, w) -> vec4;
-        xzzx(x, z, z, x) -> vec4;
-        xzzy(x, z, z, y) -> vec4;
-        xzzz(x, z, z, z) -> vec4;
-        xzzw(x, z, z, w) -> vec4;
-        xzwx(x, z, w, x) -> vec4;
-        xzwy(x, z, w, y) -> vec4;
-        xzwz(x, z, w, z) -> vec4;
-        xzww(x, z, w, w) -> vec4;
-        xwxx(x, w, x, x) -> vec4;
-        xwxy(x, w, x, y) -> vec4;
-        xwxz(x, w, x, z) -> vec4;
-        xwxw(x, w, x, w) -> vec4;
-        xwyx(x, w, y, x) -> vec4;
-        xwyy(x, w, y, y) -> vec4;
-        xwyz(x, w, y, z) -> vec4;
-        xwyw(x, w, y, w) -> vec4;
-        xwzx(x, w, z, x) -> vec4;
-        xwzy(x, w, z, y) -> vec4;
-        xwzz(x, w, z, z) -> vec4;
-        xwzw(x, w, z, w) -> vec4;
-        xwwx(x, w, w, x) -> vec4;
-        xwwy(x, w, w, y) -> vec4;
-        xwwz(x, w, w, z) -> vec4;
-        xwww(x, w, w, w) -> vec4;
-        yxxx(y, x, x, x) -> vec4;
-        yxxy(y, x, x, y) -> vec4;
-        yxxz(y, x, x, z) -> vec4;
-        yxxw(y, x, x, w) -> vec4;
-        yxyx(y, x, y, x) -> vec4;
-        yxyy(y, x, y, y) -> vec4;
-        yxyz(y, x, y, z) -> vec4;
-        yxyw(y, x, y, w) -> vec4;
-        yxzx(y, x, z, x) -> vec4;
-        yxzy(y, x, z, y) -> vec4;
-        yxzz(y, x, z, z) -> vec4;
-        yxzw(y, x, z, w) -> vec4;
-        yxwx(y, x, w, x) -> vec4;
-        yxwy(y, x, w, y) -> vec4;
-        yxwz(y, x, w, z) -> vec4;
-        yxww(y, x, w, w) -> vec4;
-        yyxx(y, y, x, x) -> vec4;
-        yyxy(y, y, x, y) -> vec4;
-        yyxz(y, y, x, z) -> vec4;
-        yyxw(y, y, x, w) -> vec4;
-        yyyx(y, y, y, x) -> vec4;
-        yyyy(y, y, y, y) -> vec4;
-        yyyz(y, y, y, z) -> vec4;
-        yyyw(y, y, y, w) -> vec4;
-        yyzx(y, y, z, x) -> vec4;
-        yyzy(y, y, z, y) -> vec4;
-        yyzz(y, y, z, z) -> vec4;
-        yyzw(y, y, z, w) -> vec4;
-        yywx(y, y, w, x) -> vec4;
-        yywy(y, y, w, y) -> vec4;
-        yywz(y, y, w, z) -> vec4;
-        yyww(y, y, w, w) -> vec4;
-        yzxx(y, z, x, x) -> vec4;
-        yzxy(y, z, x, y) -> vec4;
-        yzxz(y, z, x, z) -> vec4;
-        yzxw(y, z, x, w) -> vec4;
-        yzyx(y, z, y, x) -> vec4;
-        yzyy(y, z, y, y) -> vec4;
-        yzyz(y, z, y, z) -> vec4;
-        yzyw(y, z, y, w) -> vec4;
-        yzzx(y, z, z, x) -> vec4;
-        yzzy(y, z, z, y) -> vec4;
-        yzzz(y, z, z, z) -> vec4;
-        yzzw(y, z, z, w) -> vec4;
-        yzwx(y, z, w, x) -> vec4;
-        yzwy(y, z, w, y) -> vec4;
-        yzwz(y, z, w, z) -> vec4;
-        yzww(y, z, w, w) -> vec4;
-        ywxx(y, w, x, x) -> vec4;
-        ywxy(y, w, x, y) -> vec4;
-        ywxz(y, w, x, z) -> vec4;
-        ywxw(y, w, x, w) -> vec4;
-        ywyx(y, w, y, x) -> vec4;
-        ywyy(y, w, y, y) -> vec4;
-        ywyz(y, w, y, z) -> vec4;
-        ywyw(y, w, y, w) -> vec4;
-        ywzx(y, w, z, x) -> vec4;
-        ywzy(y, w, z, y) -> vec4;
-        ywzz(y, w, z, z) -> vec4;
-        ywzw(y, w, z, w) -> vec4;
-        ywwx(y, w, w, x) -> vec4;
-        ywwy(y, w, w, y) -> vec4;
-        ywwz(y, w, w, z) -> vec4;
-        ywww(y, w, w, w) -> vec4;
-        zxxx(z, x, x, x) -> vec4;
-        zxxy(z, x, x, y) -> vec4;
-        zxxz(z, x, x, z) -> vec4;
-        zxxw(z, x, x, w) -> vec4;
-        zxyx(z, x, y, x) -> vec4;
-        zxyy(z, x, y, y) -> vec4;
-        zxyz(z, x, y, z) -> vec4;
-        zxyw(z, x, y, w) -> vec4;
-        zxzx(z, x, z, x) -> vec4;
-        zxzy(z, x, z, y) -> vec4;
-        zxzz(z, x, z, z) -> vec4;
-        zxzw(z, x, z, w) -> vec4;
-        zxwx(z, x, w, x) -> vec4;
-        zxwy(z, x, w, y) -> vec4;
-        zxwz(z, x, w, z) -> vec4;
-        zxww(z, x, w, w) -> vec4;
-        zyxx(z, y, x, x) -> vec4;
-        zyxy(z, y, x, y) -> vec4;
-        zyxz(z, y, x, z) -> vec4;
-        zyxw(z, y, x, w) -> vec4;
-        zyyx(z, y, y, x) -> vec4;
-        zyyy(z, y, y, y) -> vec4;
-        zyyz(z, y, y, z) -> vec4;
-        zyyw(z, y, y, w) -> vec4;
-        zyzx(z, y, z, x) -> vec4;
-        zyzy(z, y, z, y) -> vec4;
-        zyzz(z, y, z, z) -> vec4;
-        zyzw(z, y, z, w) -> vec4;
-        zywx(z, y, w, x) -> vec4;
-        zywy(z, y, w, y) -> vec4;
-        zywz(z, y, w, z) -> vec4;
-        zyww(z, y, w, w) -> vec4;
-        zzxx(z, z, x, x) -> vec4;
-        zzxy(z, z, x, y) -> vec4;
-        zzxz(z, z, x, z) -> vec4;
-        zzxw(z, z, x, w) -> vec4;
-        zzyx(z, z, y, x) -> vec4;
-        zzyy(z, z, y, y) -> vec4;
-        zzyz(z, z, y, z) -> vec4;
-        zzyw(z, z, y, w) -> vec4;
-        zzzx(z, z, z, x) -> vec4;
-        zzzy(z, z, z, y) -> vec4;
-        zzzz(z, z, z, z) -> vec4;
-        zzzw(z, z, z, w) -> vec4;
-        zzwx(z, z, w, x) -> vec4;
-        zzwy(z, z, w, y) -> vec4;
-        zzwz(z, z, w, z) -> vec4;
-        zzww(z, z, w, w) -> vec4;
-        zwxx(z, w, x, x) -> vec4;
-        zwxy(z, w, x, y) -> vec4;
-        zwxz(z, w, x, z) -> vec4;
-        zwxw(z, w, x, w) -> vec4;
-        zwyx(z, w, y, x) -> vec4;
-        zwyy(z, w, y, y) -> vec4;
-        zwyz(z, w, y, z) -> vec4;
-        zwyw(z, w, y, w) -> vec4;
-        zwzx(z, w, z, x) -> vec4;
-        zwzy(z, w, z, y) -> vec4;
-        zwzz(z, w, z, z) -> vec4;
-        zwzw(z, w, z, w) -> vec4;
-        zwwx(z, w, w, x) -> vec4;
-        zwwy(z, w, w, y) -> vec4;
-        zwwz(z, w, w, z) -> vec4;
-        zwww(z, w, w, w) -> vec4;
-        wxxx(w, x, x, x) -> vec4;
-        wxxy(w, x, x, y) -> vec4;
-        wxxz(w, x, x, z) -> vec4;
-        wxxw(w, x, x, w) -> vec4;
-        wxyx(w, x, y, x) -> vec4;
-        wxyy(w, x, y, y) -> vec4;
-        wxyz(w, x, y, z) -> vec4;
-        wxyw(w, x, y, w) -> vec4;
-        wxzx(w, x, z, x) -> vec4;
-        wxzy(w, x, z, y) -> vec4;
-        wxzz(w, x, z, z) -> vec4;
-        wxzw(w, x, z, w) -> vec4;
-        wxwx(w, x, w, x) -> vec4;
-        wxwy(w, x, w, y) -> vec4;
-        wxwz(w, x, w, z) -> vec4;
-        wxww(w, x, w, w) -> vec4;
-        wyxx(w, y, x, x) -> vec4;
-        wyxy(w, y, x, y) -> vec4;
-        wyxz(w, y, x, z) -> vec4;
-        wyxw(w, y, x, w) -> vec4;
-        wyyx(w, y, y, x) -> vec4;
-        wyyy(w, y, y, y) -> vec4;
-        wyyz(w, y, y, z) -> vec4;
-        wyyw(w, y, y, w) -> vec4;
-        wyzx(w, y, z, x) -> vec4;
-        wyzy(w, y, z, y) -> vec4;
-        wyzz(w, y, z, z) -> vec4;
-        wyzw(w, y, z, w) -> vec4;
-        wywx(w, y, w, x) -> vec4;
-        wywy(w, y, w, y) -> vec4;
-        wywz(w, y, w, z) -> vec4;
-        wyww(w, y, w, w) -> vec4;
-        wzxx(w, z, x, x) -> vec4;
-        wzxy(w, z, x, y) -> vec4;
-        wzxz(w, z, x, z) -> vec4;
-        wzxw(w, z, x, w) -> vec4;
-        wzyx(w, z, y, x) -> vec4;
-        wzyy(w, z, y, y) -> vec4;
-        wzyz(w, z, y, z) -> vec4;
-        wzyw(w, z, y, w) -> vec4;
-        wzzx(w, z, z, x) -> vec4;
-        wzzy(w, z, z, y) -> vec4;
-        wzzz(w, z, z, z) -> vec4;
-        wzzw(w, z, z, w) -> vec4;
-        wzwx(w, z, w, x) -> vec4;
-        wzwy(w, z, w, y) -> vec4;
-        wzwz(w, z, w, z) -> vec4;
-        wzww(w, z, w, w) -> vec4;
-        wwxx(w, w, x, x) -> vec4;
-        wwxy(w, w, x, y) -> vec4;
-        wwxz(w, w, x, z) -> vec4;
-        wwxw(w, w, x, w) -> vec4;
-        wwyx(w, w, y, x) -> vec4;
-        wwyy(w, w, y, y) -> vec4;
-        wwyz(w, w, y, z) -> vec4;
-        wwyw(w, w, y, w) -> vec4;
-        wwzx(w, w, z, x) -> vec4;
-        wwzy(w, w, z, y) -> vec4;
-        wwzz(w, w, z, z) -> vec4;
-        wwzw(w, w, z, w) -> vec4;
-        wwwx(w, w, w, x) -> vec4;
-        wwwy(w, w, w, y) -> vec4;
-        wwwz(w, w, w, z) -> vec4;
-        wwww(w, w, w, w) -> vec4;
-    }
+                    $out::from_components(core::array::from_fn(|r| core::array::from_fn(|c| cols[c][r])))
+                }
+            }
+        )*
+    };
 }
 
-num_impl!(4 * f64: vec4(x, y, z, w));
+// Trait forwarding for the inherent `determinant` and `inverse`, so the free
+// functions in `functions` work on every square matrix type.
+macro_rules! inverse_impl {
+    ($ty:ty: $($name:ident),*) => {
+        $(
+            impl Inverse for $name {
+                type Scalar = $ty;
 
-define! {
-    self: 2 * [f64; 2];
-    mat2(mx1, mx2);
+                fn determinant(self) -> $ty { $name::determinant(self) }
+                fn inverse(self) -> $name { $name::inverse(self) }
 
-    constructor {
-        (m11: f64, m21: f64, m12: f64, m22: f64) => mat2(([m11, m21], [m12, m22]));
-    }
+                fn try_inverse(self) -> Option<$name> {
+                    let inverse = $name::inverse(self);
+                    let finite = (1.0 / $name::determinant(self)).is_finite()
+                        && inverse.iter().flatten().all(|comp| comp.is_finite());
 
-    accessor {
-        m11: f64 => self.mx1[0];
-        m21: f64 => self.mx1[1];
-        m12: f64 => self.mx2[0];
-        m22: f64 => self.mx2[1];
-    }
+                    finite.then_some(inverse)
+                }
+            }
+        )*
+    };
+}
 
-    op {
-        Mul<vec2>: {
-            type Output = vec2;
-        } mul(self: mat2, vec: vec2) -> vec2 => vec2((vec.x * self.m11() + vec.y * self.m12(), vec.x * self.m21() + vec.y * self.m22()));
-    }
+// `col * row` outer products, listed as `col_vec * row_vec -> mat`.
+macro_rules! outer {
+    ($($col_vec:ident * $row_vec:ident -> $name:ident;)*) => {
+        $(
+            impl OuterProduct<$row_vec> for $col_vec {
+                type Output = $name;
+
+                fn outer_product(self, row: $row_vec) -> $name {
+                    $name::from_components(row.components().map(|comp| (self * comp).components()))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! neg_impl {
+    ($n:literal * $ty:ty: $name:ident($($field:ident),*)) => {
+        num_impl! {
+            impl($name);
+
+            Neg: {
+                type Output = $name;
+            } neg(self: $name) -> $name => $name(($(-self.$field,)*));
+        }
+    };
+}
+
+// `%` on float vectors follows GLSL `mod`, i.e. `x - y * floor(x / y)`, so the
+// result takes the sign of the divisor rather than the dividend.
+macro_rules! mod_impl {
+    ($n:literal * $ty:ty: $name:ident($($field:ident),*)) => {
+        num_impl! {
+            impl($name);
+
+            Rem: {
+                type Output = $name;
+            } rem(self: $name, rhs: $name) -> $name => $name(($(self.$field - rhs.$field * (self.$field / rhs.$field).floor(),)*));
+            Rem<$ty>: {
+                type Output = $name;
+            } rem(self: $name, rhs: $ty) -> $name => $name(($(self.$field - rhs * (self.$field / rhs).floor(),)*));
+            RemAssign: rem_assign(self: &mut $name, rhs: $name) -> () => {
+                *self = *self % rhs;
+            };
+            RemAssign<$ty>: rem_assign(self: &mut $name, rhs: $ty) -> () => {
+                *self = *self % rhs;
+            };
+        }
+    };
+}
+
+macro_rules! bit_impl {
+    ($n:literal * $ty:ty: $name:ident($($field:ident),*)) => {
+        num_impl! {
+            impl($name);
+
+            BitAnd: {
+                type Output = $name;
+            } bitand(self: $name, rhs: $name) -> $name => $name(($(self.$field & rhs.$field,)*));
+            BitAndAssign: bitand_assign(self: &mut $name, rhs: $name) -> () => {
+                $(self.$field &= rhs.$field;)*;
+            };
+
+            BitOr: {
+                type Output = $name;
+            } bitor(self: $name, rhs: $name) -> $name => $name(($(self.$field | rhs.$field,)*));
+            BitOrAssign: bitor_assign(self: &mut $name, rhs: $name) -> () => {
+                $(self.$field |= rhs.$field;)*;
+            };
+
+            BitXor: {
+                type Output = $name;
+            } bitxor(self: $name, rhs: $name) -> $name => $name(($(self.$field ^ rhs.$field,)*));
+            BitXorAssign: bitxor_assign(self: &mut $name, rhs: $name) -> () => {
+                $(self.$field ^= rhs.$field;)*;
+            };
+
+            Not: {
+                type Output = $name;
+            } not(self: $name) -> $name => $name(($(!self.$field,)*));
+        }
+    };
+}
+
+macro_rules! int_impl {
+    ($n:literal * $ty:ty: $name:ident($($field:ident),*)) => {
+        bit_impl!($n * $ty: $name($($field),*));
+
+        num_impl! {
+            impl($name);
+
+            BitAnd<$ty>: {
+                type Output = $name;
+            } bitand(self: $name, rhs: $ty) -> $name => $name(($(self.$field & rhs,)*));
+            BitOr<$ty>: {
+                type Output = $name;
+            } bitor(self: $name, rhs: $ty) -> $name => $name(($(self.$field | rhs,)*));
+            BitXor<$ty>: {
+                type Output = $name;
+            } bitxor(self: $name, rhs: $ty) -> $name => $name(($(self.$field ^ rhs,)*));
+
+            Rem: {
+                type Output = $name;
+            } rem(self: $name, rhs: $name) -> $name => $name(($(self.$field % rhs.$field,)*));
+            Rem<$ty>: {
+                type Output = $name;
+            } rem(self: $name, rhs: $ty) -> $name => $name(($(self.$field % rhs,)*));
+            RemAssign: rem_assign(self: &mut $name, rhs: $name) -> () => {
+                $(self.$field %= rhs.$field;)*;
+            };
+            RemAssign<$ty>: rem_assign(self: &mut $name, rhs: $ty) -> () => {
+                $(self.$field %= rhs;)*;
+            };
+
+            Shl: {
+                type Output = $name;
+            } shl(self: $name, rhs: $name) -> $name => $name(($(self.$field << rhs.$field,)*));
+            Shl<$ty>: {
+                type Output = $name;
+            } shl(self: $name, rhs: $ty) -> $name => $name(($(self.$field << rhs,)*));
+            ShlAssign: shl_assign(self: &mut $name, rhs: $name) -> () => {
+                $(self.$field <<= rhs.$field;)*;
+            };
+            ShlAssign<$ty>: shl_assign(self: &mut $name, rhs: $ty) -> () => {
+                $(self.$field <<= rhs;)*;
+            };
+
+            Shr: {
+                type Output = $name;
+            } shr(self: $name, rhs: $name) -> $name => $name(($(self.$field >> rhs.$field,)*));
+            Shr<$ty>: {
+                type Output = $name;
+            } shr(self: $name, rhs: $ty) -> $name => $name(($(self.$field >> rhs,)*));
+            ShrAssign: shr_assign(self: &mut $name, rhs: $name) -> () => {
+                $(self.$field >>= rhs.$field;)*;
+            };
+            ShrAssign<$ty>: shr_assign(self: &mut $name, rhs: $ty) -> () => {
+                $(self.$field >>= rhs;)*;
+            };
+        }
+
+        impl $name {
+            // GLSL integer arithmetic wraps on overflow; the plain operators
+            // keep Rust's overflow checks in debug builds.
+            pub fn wrapping_add(self, rhs: $name) -> $name { $name(($(self.$field.wrapping_add(rhs.$field),)*)) }
+            pub fn wrapping_sub(self, rhs: $name) -> $name { $name(($(self.$field.wrapping_sub(rhs.$field),)*)) }
+            pub fn wrapping_mul(self, rhs: $name) -> $name { $name(($(self.$field.wrapping_mul(rhs.$field),)*)) }
+
+            pub fn checked_add(self, rhs: $name) -> Option<$name> { Some($name(($(self.$field.checked_add(rhs.$field)?,)*))) }
+            pub fn checked_sub(self, rhs: $name) -> Option<$name> { Some($name(($(self.$field.checked_sub(rhs.$field)?,)*))) }
+            pub fn checked_mul(self, rhs: $name) -> Option<$name> { Some($name(($(self.$field.checked_mul(rhs.$field)?,)*))) }
+            pub fn checked_div(self, rhs: $name) -> Option<$name> { Some($name(($(self.$field.checked_div(rhs.$field)?,)*))) }
+        }
+    };
+}
+
+// Write-back handle returned by mutable swizzles such as `xy_mut`, so that
+// `*v.zx_mut() += w` reads like GLSL's `v.zx += w`. The write happens on drop,
+// so a handle leaked with `mem::forget` silently leaves the vector unchanged.
+pub struct SwizzleMut<'a, V, S: Copy> {
+    target: &'a mut V,
+    value: S,
+    store: fn(&mut V, S),
+}
+
+impl<'a, V: Copy, S: Copy> SwizzleMut<'a, V, S> {
+    fn new(target: &'a mut V, load: fn(V) -> S, store: fn(&mut V, S)) -> Self {
+        let value = load(*target);
+
+        Self { target, value, store }
+    }
+}
+
+impl<V, S: Copy> Deref for SwizzleMut<'_, V, S> {
+    type Target = S;
+
+    fn deref(&self) -> &S {
+        &self.value
+    }
+}
+
+impl<V, S: Copy> DerefMut for SwizzleMut<'_, V, S> {
+    fn deref_mut(&mut self) -> &mut S {
+        &mut self.value
+    }
+}
+
+impl<V, S: Copy> Drop for SwizzleMut<'_, V, S> {
+    fn drop(&mut self) {
+        (self.store)(self.target, self.value);
+    }
+}
+
+// Generates every swizzle of length 2 to 4 over the given components, under the
+// `xyzw`, `rgba` and `stpq` names. Each component is given as `(field rgba stpq)`.
+// Getters are emitted for all combinations; setters and `_mut` handles only for
+// combinations without repeated components, so `set_xx` does not exist.
+macro_rules! swizzle {
+    ($name:ident -> $v2:ident, $v3:ident, $v4:ident; $($comp:tt)*) => {
+        swizzle!(load $name [_ $v2 $v3 $v4] [] [$($comp)*] [$($comp)*]);
+        swizzle!(store $name [_ $v2 $v3 $v4] [] [] [$($comp)*]);
+    };
+
+    // every continuation of `prefix` by any component
+    (load $name:ident [] $($rest:tt)*) => {};
+    (load $name:ident $types:tt $prefix:tt $all:tt [$($comp:tt)*]) => {
+        $(swizzle!(load_push $name $types $prefix $comp $all);)*
+    };
+    (load_push $name:ident [$ty:tt $($types:tt)*] [$($prefix:tt)*] $comp:tt $all:tt) => {
+        swizzle!(load_emit $name $ty $($prefix)* $comp);
+        swizzle!(load $name [$($types)*] [$($prefix)* $comp] $all $all);
+    };
+
+    // every continuation of `prefix` by a component it does not contain yet
+    (store $name:ident [] $($rest:tt)*) => {};
+    (store $name:ident $types:tt $prefix:tt $before:tt []) => {};
+    (store $name:ident $types:tt $prefix:tt [$($before:tt)*] [$comp:tt $($after:tt)*]) => {
+        swizzle!(store_push $name $types $prefix $comp [$($before)* $($after)*]);
+        swizzle!(store $name $types $prefix [$($before)* $comp] [$($after)*]);
+    };
+    (store_push $name:ident [$ty:tt $($types:tt)*] [$($prefix:tt)*] $comp:tt $unused:tt) => {
+        swizzle!(store_emit $name $ty $($prefix)* $comp);
+        swizzle!(store $name [$($types)*] [$($prefix)* $comp] [] $unused);
+    };
+
+    (load_emit $name:ident _ $($comp:tt)*) => {};
+    (load_emit $name:ident $ty:ident $(($field:ident $rgba:ident $stpq:ident))*) => {
+        paste::paste! {
+            impl $name {
+                pub fn [<$($field)*>](self) -> $ty { $ty(($(self.$field,)*)) }
+                pub fn [<$($rgba)*>](self) -> $ty { self.[<$($field)*>]() }
+                pub fn [<$($stpq)*>](self) -> $ty { self.[<$($field)*>]() }
+            }
+        }
+    };
+
+    (store_emit $name:ident _ $($comp:tt)*) => {};
+    (store_emit $name:ident $ty:ident $(($field:ident $rgba:ident $stpq:ident))*) => {
+        paste::paste! {
+            impl $name {
+                pub fn [<set_ $($field)*>](&mut self, value: $ty) { [$(self.$field),*] = value.components(); }
+                pub fn [<set_ $($rgba)*>](&mut self, value: $ty) { self.[<set_ $($field)*>](value) }
+                pub fn [<set_ $($stpq)*>](&mut self, value: $ty) { self.[<set_ $($field)*>](value) }
+
+                pub fn [<$($field)* _mut>](&mut self) -> SwizzleMut<'_, $name, $ty> {
+                    SwizzleMut::new(self, $name::[<$($field)*>], $name::[<set_ $($field)*>])
+                }
+                pub fn [<$($rgba)* _mut>](&mut self) -> SwizzleMut<'_, $name, $ty> { self.[<$($field)* _mut>]() }
+                pub fn [<$($stpq)* _mut>](&mut self) -> SwizzleMut<'_, $name, $ty> { self.[<$($field)* _mut>]() }
+            }
+        }
+    };
+}
+
+macro_rules! argument_impl {
+    ($ty:ty: $($name:ident = $n:literal),*) => {
+        $(
+            impl Argument<$ty> for $name {
+                const LEN: usize = $n;
+
+                fn flatten(self) -> impl Iterator<Item = $ty> {
+                    self.into_iter()
+                }
+            }
+        )*
+    };
+}
+
+// GLSL-style constructors that consume their arguments' components in order,
+// e.g. `vec4((vec2, vec2))`, `vec2((vec3,))` or `mat2((vec2, vec2))`. The names are
+// one per component, and the first `k` of them name the arguments of the `k`-ary
+// constructor. Any mix of scalars and vectors is accepted as long as it covers
+// every component and only the last argument is truncated, which is checked when
+// the constructor is instantiated. Single arguments are listed separately, as they
+// would overlap the splat and identity constructors.
+macro_rules! flatten {
+    ($fill:ident $name:ident: $ty:ty; $first:ident $($comp:ident)*; $($from:ident),*) => {
+        $(
+            impl Constructor<($from,)> for $name {
+                fn new((from,): ($from,)) -> Self {
+                    let mut comps = from.flatten();
+
+                    Self::from_components(flatten!($fill comps))
+                }
+            }
+        )*
+
+        flatten!(args $fill $name: $ty; [$first $($comp)*] [$first] $($comp)*);
+    };
+
+    (args $fill:ident $name:ident: $ty:ty; $all:tt [$($para:ident)*]) => {};
+    (args $fill:ident $name:ident: $ty:ty; [$($all:ident)*] [$($para:ident)*] $last:ident $($rest:ident)*) => {
+        paste::paste! {
+            impl<$([<$para:upper>]: Argument<$ty>,)* [<$last:upper>]: Argument<$ty>>
+                Constructor<($([<$para:upper>],)* [<$last:upper>],)> for $name
+            {
+                fn new(($($para,)* $last,): ($([<$para:upper>],)* [<$last:upper>],)) -> Self {
+                    const {
+                        let len = define!(len [$($all),*]);
+                        let head = 0 $(+ [<$para:upper>]::LEN)*;
+
+                        assert!(
+                            head < len && head + [<$last:upper>]::LEN >= len,
+                            "constructor arguments must cover every component, truncating only the last",
+                        );
+                    }
+
+                    let mut comps = core::iter::empty()$(.chain($para.flatten()))*.chain($last.flatten());
+
+                    Self::from_components(flatten!($fill comps))
+                }
+            }
+        }
+
+        flatten!(args $fill $name: $ty; [$($all)*] [$($para)* $last] $($rest)*);
+    };
+
+    (vec $comps:ident) => {
+        core::array::from_fn(|_| $comps.next().unwrap())
+    };
+    (mat $comps:ident) => {
+        core::array::from_fn(|_| core::array::from_fn(|_| $comps.next().unwrap()))
+    };
+}
+
+// GLSL-style matrix-from-matrix constructors: the upper left block is copied and
+// anything the source does not cover is taken from the identity matrix.
+macro_rules! resize {
+    ($($name:ident: $($from:ident),*;)*) => {
+        $($(
+            impl Constructor<($from,)> for $name {
+                fn new((from,): ($from,)) -> Self {
+                    let from = from.components();
+
+                    Self::from_components(core::array::from_fn(|c| {
+                        core::array::from_fn(|r| match from.get(c).and_then(|col| col.get(r)) {
+                            Some(&comp) => comp,
+                            None if c == r => 1.0,
+                            None => 0.0,
+                        })
+                    }))
+                }
+            }
+        )*)*
+    };
+}
+
+macro_rules! vec_family {
+    {
+        $(#[$meta:meta])*
+        $ty:ty: $vec2:ident, $vec3:ident, $vec4:ident;
+        $($impl:ident),*
+    } => {
+        define! {
+            self: 2 * $ty;
+            $(#[$meta])*
+            $vec2(x, y);
+
+            constructor {
+                (single: $ty) => $vec2((single, single));
+            }
+        }
+
+        swizzle!($vec2 -> $vec2, $vec3, $vec4; (x r s) (y g t));
+        $($impl!(2 * $ty: $vec2(x, y));)*
+
+        impl Relational<2> for $vec2 {
+            type Bool = bvec2;
+        }
+
+        define! {
+            self: 3 * $ty;
+            $(#[$meta])*
+            $vec3(x, y, z);
+
+            constructor {
+                (single: $ty) => $vec3((single, single, single));
+            }
+        }
+
+        swizzle!($vec3 -> $vec2, $vec3, $vec4; (x r s) (y g t) (z b p));
+        $($impl!(3 * $ty: $vec3(x, y, z));)*
+
+        impl Relational<3> for $vec3 {
+            type Bool = bvec3;
+        }
+
+        define! {
+            self: 4 * $ty;
+            $(#[$meta])*
+            $vec4(x, y, z, w);
+
+            constructor {
+                (single: $ty) => $vec4((single, single, single, single));
+            }
+        }
+
+        swizzle!($vec4 -> $vec2, $vec3, $vec4; (x r s) (y g t) (z b p) (w a q));
+        $($impl!(4 * $ty: $vec4(x, y, z, w));)*
+
+        impl Relational<4> for $vec4 {
+            type Bool = bvec4;
+        }
+
+        argument_impl!($ty: $vec2 = 2, $vec3 = 3, $vec4 = 4);
+
+        flatten!(vec $vec2: $ty; x y; $vec3, $vec4);
+        flatten!(vec $vec3: $ty; x y z; $vec4);
+        flatten!(vec $vec4: $ty; x y z w;);
+    };
+}
+
+macro_rules! mat_family {
+    {
+        $ty:ty: $mat2:ident, $mat3:ident, $mat4:ident,
+        $mat2x3:ident, $mat3x2:ident, $mat2x4:ident, $mat4x2:ident, $mat3x4:ident, $mat4x3:ident;
+        $vec2:ident, $vec3:ident, $vec4:ident
+    } => {
+        define! {
+            self: 2 * [$ty; 2];
+            $mat2(mx1, mx2);
+
+            constructor {
+                (mx1: [$ty; 2], mx2: [$ty; 2]) => $mat2 { mx1, mx2 };
+            }
+
+            accessor {
+                m11: $ty => self.mx1[0];
+                m21: $ty => self.mx1[1];
+                m12: $ty => self.mx2[0];
+                m22: $ty => self.mx2[1];
+            }
+
+            assosciated {
+                pub fn determinant(self) -> $ty {
+                    self.m11() * self.m22() - self.m12() * self.m21()
+                }
+
+                pub fn inverse(self) -> $mat2 {
+                    $mat2((self.m22(), -self.m21(), -self.m12(), self.m11())) * (1.0 / self.determinant())
+                }
+            }
+        }
+
+        mat_impl!($ty: $mat2(mx1, mx2): $vec2 * $vec2(x, y));
+        square_impl!($mat2: $vec2);
+
+        define! {
+            self: 3 * [$ty; 3];
+            $mat3(mx1, mx2, mx3);
+
+            constructor {
+                (mx1: [$ty; 3], mx2: [$ty; 3], mx3: [$ty; 3]) => $mat3 { mx1, mx2, mx3 };
+            }
+
+            accessor {
+                m11: $ty => self.mx1[0];
+                m21: $ty => self.mx1[1];
+                m31: $ty => self.mx1[2];
+                m12: $ty => self.mx2[0];
+                m22: $ty => self.mx2[1];
+                m32: $ty => self.mx2[2];
+                m13: $ty => self.mx3[0];
+                m23: $ty => self.mx3[1];
+                m33: $ty => self.mx3[2];
+            }
+
+            assosciated {
+                pub fn determinant(self) -> $ty {
+                    self.m11() * (self.m22() * self.m33() - self.m23() * self.m32())
+                        - self.m12() * (self.m21() * self.m33() - self.m23() * self.m31())
+                        + self.m13() * (self.m21() * self.m32() - self.m22() * self.m31())
+                }
+
+                pub fn inverse(self) -> $mat3 {
+                    let [[a, d, g], [b, e, h], [c, f, i]] = self.components();
+
+                    $mat3((
+                        e * i - f * h, f * g - d * i, d * h - e * g,
+                        c * h - b * i, a * i - c * g, b * g - a * h,
+                        b * f - c * e, c * d - a * f, a * e - b * d,
+                    )) * (1.0 / self.determinant())
+                }
+            }
+        }
+
+        mat_impl!($ty: $mat3(mx1, mx2, mx3): $vec3 * $vec3(x, y, z));
+        square_impl!($mat3: $vec3);
+
+        define! {
+            self: 4 * [$ty; 4];
+            $mat4(mx1, mx2, mx3, mx4);
+
+            constructor {
+                (mx1: [$ty; 4], mx2: [$ty; 4], mx3: [$ty; 4], mx4: [$ty; 4]) => $mat4 { mx1, mx2, mx3, mx4 };
+            }
+
+            accessor {
+                m11: $ty => self.mx1[0];
+                m21: $ty => self.mx1[1];
+                m31: $ty => self.mx1[2];
+                m41: $ty => self.mx1[3];
+                m12: $ty => self.mx2[0];
+                m22: $ty => self.mx2[1];
+                m32: $ty => self.mx2[2];
+                m42: $ty => self.mx2[3];
+                m13: $ty => self.mx3[0];
+                m23: $ty => self.mx3[1];
+                m33: $ty => self.mx3[2];
+                m43: $ty => self.mx3[3];
+                m14: $ty => self.mx4[0];
+                m24: $ty => self.mx4[1];
+                m34: $ty => self.mx4[2];
+                m44: $ty => self.mx4[3];
+            }
+
+            assosciated {
+                // 2x2 minors of the upper (s*) and lower (c*) row pairs, shared by
+                // the determinant and the adjugate.
+                fn minors(self) -> ([$ty; 6], [$ty; 6]) {
+                    let [[a00, a10, a20, a30], [a01, a11, a21, a31], [a02, a12, a22, a32], [a03, a13, a23, a33]] =
+                        self.components();
+
+                    (
+                        [
+                            a00 * a11 - a10 * a01,
+                            a00 * a12 - a10 * a02,
+                            a00 * a13 - a10 * a03,
+                            a01 * a12 - a11 * a02,
+                            a01 * a13 - a11 * a03,
+                            a02 * a13 - a12 * a03,
+                        ],
+                        [
+                            a20 * a31 - a30 * a21,
+                            a20 * a32 - a30 * a22,
+                            a20 * a33 - a30 * a23,
+                            a21 * a32 - a31 * a22,
+                            a21 * a33 - a31 * a23,
+                            a22 * a33 - a32 * a23,
+                        ],
+                    )
+                }
+
+                pub fn determinant(self) -> $ty {
+                    let ([s0, s1, s2, s3, s4, s5], [c0, c1, c2, c3, c4, c5]) = self.minors();
+
+                    s0 * c5 - s1 * c4 + s2 * c3 + s3 * c2 - s4 * c1 + s5 * c0
+                }
+
+                pub fn inverse(self) -> $mat4 {
+                    let [[a00, a10, a20, a30], [a01, a11, a21, a31], [a02, a12, a22, a32], [a03, a13, a23, a33]] =
+                        self.components();
+                    let ([s0, s1, s2, s3, s4, s5], [c0, c1, c2, c3, c4, c5]) = self.minors();
+
+                    $mat4((
+                        a11 * c5 - a12 * c4 + a13 * c3,
+                        -a10 * c5 + a12 * c2 - a13 * c1,
+                        a10 * c4 - a11 * c2 + a13 * c0,
+                        -a10 * c3 + a11 * c1 - a12 * c0,
+                        -a01 * c5 + a02 * c4 - a03 * c3,
+                        a00 * c5 - a02 * c2 + a03 * c1,
+                        -a00 * c4 + a01 * c2 - a03 * c0,
+                        a00 * c3 - a01 * c1 + a02 * c0,
+                        a31 * s5 - a32 * s4 + a33 * s3,
+                        -a30 * s5 + a32 * s2 - a33 * s1,
+                        a30 * s4 - a31 * s2 + a33 * s0,
+                        -a30 * s3 + a31 * s1 - a32 * s0,
+                        -a21 * s5 + a22 * s4 - a23 * s3,
+                        a20 * s5 - a22 * s2 + a23 * s1,
+                        -a20 * s4 + a21 * s2 - a23 * s0,
+                        a20 * s3 - a21 * s1 + a22 * s0,
+                    )) * (1.0 / self.determinant())
+                }
+            }
+        }
+
+        mat_impl!($ty: $mat4(mx1, mx2, mx3, mx4): $vec4 * $vec4(x, y, z, w));
+        square_impl!($mat4: $vec4);
+
+        define! {
+            self: 2 * [$ty; 3];
+            $mat2x3(mx1, mx2);
+
+            constructor {
+                (mx1: [$ty; 3], mx2: [$ty; 3]) => $mat2x3 { mx1, mx2 };
+            }
+
+            accessor {
+                m11: $ty => self.mx1[0];
+                m21: $ty => self.mx1[1];
+                m31: $ty => self.mx1[2];
+                m12: $ty => self.mx2[0];
+                m22: $ty => self.mx2[1];
+                m32: $ty => self.mx2[2];
+            }
+        }
+
+        mat_impl!($ty: $mat2x3(mx1, mx2): $vec3 * $vec2(x, y));
+
+        define! {
+            self: 3 * [$ty; 2];
+            $mat3x2(mx1, mx2, mx3);
+
+            constructor {
+                (mx1: [$ty; 2], mx2: [$ty; 2], mx3: [$ty; 2]) => $mat3x2 { mx1, mx2, mx3 };
+            }
+
+            accessor {
+                m11: $ty => self.mx1[0];
+                m21: $ty => self.mx1[1];
+                m12: $ty => self.mx2[0];
+                m22: $ty => self.mx2[1];
+                m13: $ty => self.mx3[0];
+                m23: $ty => self.mx3[1];
+            }
+        }
+
+        mat_impl!($ty: $mat3x2(mx1, mx2, mx3): $vec2 * $vec3(x, y, z));
+
+        define! {
+            self: 2 * [$ty; 4];
+            $mat2x4(mx1, mx2);
+
+            constructor {
+                (mx1: [$ty; 4], mx2: [$ty; 4]) => $mat2x4 { mx1, mx2 };
+            }
+
+            accessor {
+                m11: $ty => self.mx1[0];
+                m21: $ty => self.mx1[1];
+                m31: $ty => self.mx1[2];
+                m41: $ty => self.mx1[3];
+                m12: $ty => self.mx2[0];
+                m22: $ty => self.mx2[1];
+                m32: $ty => self.mx2[2];
+                m42: $ty => self.mx2[3];
+            }
+        }
+
+        mat_impl!($ty: $mat2x4(mx1, mx2): $vec4 * $vec2(x, y));
+
+        define! {
+            self: 4 * [$ty; 2];
+            $mat4x2(mx1, mx2, mx3, mx4);
+
+            constructor {
+                (mx1: [$ty; 2], mx2: [$ty; 2], mx3: [$ty; 2], mx4: [$ty; 2]) => $mat4x2 { mx1, mx2, mx3, mx4 };
+            }
+
+            accessor {
+                m11: $ty => self.mx1[0];
+                m21: $ty => self.mx1[1];
+                m12: $ty => self.mx2[0];
+                m22: $ty => self.mx2[1];
+                m13: $ty => self.mx3[0];
+                m23: $ty => self.mx3[1];
+                m14: $ty => self.mx4[0];
+                m24: $ty => self.mx4[1];
+            }
+        }
+
+        mat_impl!($ty: $mat4x2(mx1, mx2, mx3, mx4): $vec2 * $vec4(x, y, z, w));
+
+        define! {
+            self: 3 * [$ty; 4];
+            $mat3x4(mx1, mx2, mx3);
+
+            constructor {
+                (mx1: [$ty; 4], mx2: [$ty; 4], mx3: [$ty; 4]) => $mat3x4 { mx1, mx2, mx3 };
+            }
+
+            accessor {
+                m11: $ty => self.mx1[0];
+                m21: $ty => self.mx1[1];
+                m31: $ty => self.mx1[2];
+                m41: $ty => self.mx1[3];
+                m12: $ty => self.mx2[0];
+                m22: $ty => self.mx2[1];
+                m32: $ty => self.mx2[2];
+                m42: $ty => self.mx2[3];
+                m13: $ty => self.mx3[0];
+                m23: $ty => self.mx3[1];
+                m33: $ty => self.mx3[2];
+                m43: $ty => self.mx3[3];
+            }
+        }
+
+        mat_impl!($ty: $mat3x4(mx1, mx2, mx3): $vec4 * $vec3(x, y, z));
+
+        define! {
+            self: 4 * [$ty; 3];
+            $mat4x3(mx1, mx2, mx3, mx4);
+
+            constructor {
+                (mx1: [$ty; 3], mx2: [$ty; 3], mx3: [$ty; 3], mx4: [$ty; 3]) => $mat4x3 { mx1, mx2, mx3, mx4 };
+            }
+
+            accessor {
+                m11: $ty => self.mx1[0];
+                m21: $ty => self.mx1[1];
+                m31: $ty => self.mx1[2];
+                m12: $ty => self.mx2[0];
+                m22: $ty => self.mx2[1];
+                m32: $ty => self.mx2[2];
+                m13: $ty => self.mx3[0];
+                m23: $ty => self.mx3[1];
+                m33: $ty => self.mx3[2];
+                m14: $ty => self.mx4[0];
+                m24: $ty => self.mx4[1];
+                m34: $ty => self.mx4[2];
+            }
+        }
+
+        mat_impl!($ty: $mat4x3(mx1, mx2, mx3, mx4): $vec3 * $vec4(x, y, z, w));
+
+        flatten!(mat $mat2: $ty; m11 m21 m12 m22; $vec4);
+        flatten!(mat $mat3: $ty; m11 m21 m31 m12 m22 m32 m13 m23 m33;);
+        flatten!(mat $mat4: $ty; m11 m21 m31 m41 m12 m22 m32 m42 m13 m23 m33 m43 m14 m24 m34 m44;);
+        flatten!(mat $mat2x3: $ty; m11 m21 m31 m12 m22 m32;);
+        flatten!(mat $mat3x2: $ty; m11 m21 m12 m22 m13 m23;);
+        flatten!(mat $mat2x4: $ty; m11 m21 m31 m41 m12 m22 m32 m42;);
+        flatten!(mat $mat4x2: $ty; m11 m21 m12 m22 m13 m23 m14 m24;);
+        flatten!(mat $mat3x4: $ty; m11 m21 m31 m41 m12 m22 m32 m42 m13 m23 m33 m43;);
+        flatten!(mat $mat4x3: $ty; m11 m21 m31 m12 m22 m32 m13 m23 m33 m14 m24 m34;);
+
+        mat_mul! {
+            $mat2: $vec2 * ($mat2 -> $mat2, $mat3x2 -> $mat3x2, $mat4x2 -> $mat4x2);
+            $mat2x3: $vec2 * ($mat2 -> $mat2x3, $mat3x2 -> $mat3, $mat4x2 -> $mat4x3);
+            $mat2x4: $vec2 * ($mat2 -> $mat2x4, $mat3x2 -> $mat3x4, $mat4x2 -> $mat4);
+            $mat3x2: $vec3 * ($mat2x3 -> $mat2, $mat3 -> $mat3x2, $mat4x3 -> $mat4x2);
+            $mat3: $vec3 * ($mat2x3 -> $mat2x3, $mat3 -> $mat3, $mat4x3 -> $mat4x3);
+            $mat3x4: $vec3 * ($mat2x3 -> $mat2x4, $mat3 -> $mat3x4, $mat4x3 -> $mat4);
+            $mat4x2: $vec4 * ($mat2x4 -> $mat2, $mat3x4 -> $mat3x2, $mat4 -> $mat4x2);
+            $mat4x3: $vec4 * ($mat2x4 -> $mat2x3, $mat3x4 -> $mat3, $mat4 -> $mat4x3);
+            $mat4: $vec4 * ($mat2x4 -> $mat2x4, $mat3x4 -> $mat3x4, $mat4 -> $mat4);
+        }
+
+        transpose_impl!(
+            $mat2 -> $mat2, $mat3 -> $mat3, $mat4 -> $mat4,
+            $mat2x3 -> $mat3x2, $mat3x2 -> $mat2x3,
+            $mat2x4 -> $mat4x2, $mat4x2 -> $mat2x4,
+            $mat3x4 -> $mat4x3, $mat4x3 -> $mat3x4
+        );
+        inverse_impl!($ty: $mat2, $mat3, $mat4);
+
+        outer! {
+            $vec2 * $vec2 -> $mat2;
+            $vec3 * $vec2 -> $mat2x3;
+            $vec4 * $vec2 -> $mat2x4;
+            $vec2 * $vec3 -> $mat3x2;
+            $vec3 * $vec3 -> $mat3;
+            $vec4 * $vec3 -> $mat3x4;
+            $vec2 * $vec4 -> $mat4x2;
+            $vec3 * $vec4 -> $mat4x3;
+            $vec4 * $vec4 -> $mat4;
+        }
+
+        resize! {
+            $mat2: $mat2x3, $mat2x4, $mat3x2, $mat3, $mat3x4, $mat4x2, $mat4x3, $mat4;
+            $mat2x3: $mat2, $mat2x4, $mat3x2, $mat3, $mat3x4, $mat4x2, $mat4x3, $mat4;
+            $mat2x4: $mat2, $mat2x3, $mat3x2, $mat3, $mat3x4, $mat4x2, $mat4x3, $mat4;
+            $mat3x2: $mat2, $mat2x3, $mat2x4, $mat3, $mat3x4, $mat4x2, $mat4x3, $mat4;
+            $mat3: $mat2, $mat2x3, $mat2x4, $mat3x2, $mat3x4, $mat4x2, $mat4x3, $mat4;
+            $mat3x4: $mat2, $mat2x3, $mat2x4, $mat3x2, $mat3, $mat4x2, $mat4x3, $mat4;
+            $mat4x2: $mat2, $mat2x3, $mat2x4, $mat3x2, $mat3, $mat3x4, $mat4x3, $mat4;
+            $mat4x3: $mat2, $mat2x3, $mat2x4, $mat3x2, $mat3, $mat3x4, $mat4x2, $mat4;
+            $mat4: $mat2, $mat2x3, $mat2x4, $mat3x2, $mat3, $mat3x4, $mat4x2, $mat4x3;
+        }
+    };
+}
+
+vec_family! {
+    f32: vec2, vec3, vec4;
+    num_impl, neg_impl, mod_impl
+}
+
+vec_family! {
+    f64: dvec2, dvec3, dvec4;
+    num_impl, neg_impl, mod_impl
+}
+
+vec_family! {
+    #[derive(Eq, Hash, PartialOrd, Ord)]
+    i32: ivec2, ivec3, ivec4;
+    num_impl, neg_impl, int_impl
+}
+
+vec_family! {
+    #[derive(Eq, Hash, PartialOrd, Ord)]
+    u32: uvec2, uvec3, uvec4;
+    num_impl, int_impl
+}
+
+vec_family! {
+    #[derive(Eq, Hash, PartialOrd, Ord)]
+    bool: bvec2, bvec3, bvec4;
+    bit_impl
+}
+
+mat_family! {
+    f32: mat2, mat3, mat4, mat2x3, mat3x2, mat2x4, mat4x2, mat3x4, mat4x3;
+    vec2, vec3, vec4
+}
+
+mat_family! {
+    f64: dmat2, dmat3, dmat4, dmat2x3, dmat3x2, dmat2x4, dmat4x2, dmat3x4, dmat4x3;
+    dvec2, dvec3, dvec4
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Reads and writes every component through `as_array`, `as_mut_array`, `Index`,
+    // `IndexMut`, `iter` and `iter_mut`, which all go through the two casts above.
+    macro_rules! access_test {
+        ($test:ident: $($name:ident),* => |$i:ident| $comp:expr) => {
+            #[test]
+            fn $test() {
+                $({
+                    let comps = core::array::from_fn(|$i| $comp);
+                    let mut value = <$name as Components<_>>::from_components(comps);
+
+                    assert_eq!(*value.as_array(), comps);
+                    assert!((0..comps.len()).all(|i| value[i] == comps[i]));
+                    assert!(value.iter().eq(comps.iter()));
+
+                    let mut reversed = comps;
+                    reversed.reverse();
+
+                    *value.as_mut_array() = reversed;
+                    assert_eq!(value.components(), reversed);
+
+                    for i in 0..comps.len() {
+                        value[i] = comps[i];
+                    }
+                    assert_eq!(value.components(), comps);
+
+                    for (comp, new) in value.iter_mut().zip(reversed) {
+                        *comp = new;
+                    }
+                    assert_eq!(value.components(), reversed);
+                })*
+            }
+        };
+    }
+
+    #[test]
+    fn mat_col() {
+        let m = mat3x2((1.0, 2.0, 3.0, 4.0, 5.0, 6.0));
+
+        assert_eq!(m[1], [3.0, 4.0]);
+        assert_eq!(m.col(1), vec2((3.0, 4.0)));
+        assert_eq!(m * vec3((0.0, 1.0, 0.0)), m.col(1));
+    }
+
+    #[test]
+    fn flatten_order() {
+        let v2 = vec2((1.0, 2.0));
+        let v3 = vec3((3.0, 4.0, 5.0));
+        let v4 = vec4((6.0, 7.0, 8.0, 9.0));
+
+        assert_eq!(vec2((v3,)), vec2((3.0, 4.0)));
+        assert_eq!(vec2((v4,)), vec2((6.0, 7.0)));
+        assert_eq!(vec2((0.0, v2)), vec2((0.0, 1.0)));
+        assert_eq!(vec2((0.0, v4)), vec2((0.0, 6.0)));
+        assert_eq!(vec3((v4,)), vec3((6.0, 7.0, 8.0)));
+        assert_eq!(vec3((v2, v2)), vec3((1.0, 2.0, 1.0)));
+        assert_eq!(vec3((0.0, v3)), vec3((0.0, 3.0, 4.0)));
+        assert_eq!(vec3((v2, 0.0)), vec3((1.0, 2.0, 0.0)));
+        assert_eq!(vec3((0.0, 0.5, v4)), vec3((0.0, 0.5, 6.0)));
+        assert_eq!(vec4((v3, v2)), vec4((3.0, 4.0, 5.0, 1.0)));
+        assert_eq!(vec4((v2, v3)), vec4((1.0, 2.0, 3.0, 4.0)));
+        assert_eq!(vec4((0.0, v2, v4)), vec4((0.0, 1.0, 2.0, 6.0)));
+        assert_eq!(vec4((v2, 0.0, 0.5)), vec4((1.0, 2.0, 0.0, 0.5)));
+        assert_eq!(ivec3((ivec2((1, 2)), 3)), ivec3((1, 2, 3)));
+        assert_eq!(
+            bvec4((true, bvec3((false,)))),
+            bvec4((true, false, false, false))
+        );
+
+        let m = mat2((1.0, 2.0, 3.0, 4.0));
+
+        assert_eq!(mat2((v4,)), mat2((6.0, 7.0, 8.0, 9.0)));
+        assert_eq!(mat2((0.0, v3)), mat2((0.0, 3.0, 4.0, 5.0)));
+        assert_eq!(mat2((v2, vec2((3.0, 4.0)))), m);
+        assert_eq!(mat2((1.0, v4)), mat2((1.0, 6.0, 7.0, 8.0)));
+        assert_eq!(m.col(0), v2);
+        assert_eq!(mat2x3((v4, v2)), mat2x3((6.0, 7.0, 8.0, 9.0, 1.0, 2.0)));
+        assert_eq!(mat3((v3, v3, v3)).col(2), v3);
+        assert_eq!(
+            mat3((v4, v4, 0.0)),
+            mat3((6.0, 7.0, 8.0, 9.0, 6.0, 7.0, 8.0, 9.0, 0.0))
+        );
+        assert_eq!(mat4((v4, v4, v4, v4)).col(3), v4);
+        assert_eq!(
+            dmat3x2((dvec3((1.0,)), dvec3((2.0,)))),
+            dmat3x2((1.0, 1.0, 1.0, 2.0, 2.0, 2.0))
+        );
+    }
+
+    #[test]
+    fn swizzle_mut() {
+        let mut v = vec3((1.0, 2.0, 3.0));
+
+        *v.zx_mut() += vec2((10.0, 20.0));
+        assert_eq!(v, vec3((21.0, 2.0, 13.0)));
+
+        v.set_bg(vec2((5.0, 6.0)));
+        assert_eq!(v, vec3((21.0, 6.0, 5.0)));
+
+        *v.ps_mut() = vec2((0.0, 1.0));
+        assert_eq!(v.stp(), vec3((1.0, 6.0, 0.0)));
+        assert_eq!(v.pts(), v.zyx());
+
+        let mut c = vec4((0.1, 0.2, 0.3, 0.4));
+        c.set_qs(vec2((1.0, 0.0)));
+        assert_eq!(c.argb(), vec4((1.0, 0.0, 0.2, 0.3)));
+    }
+
+    // Checks `m * inverse(m)` and `inverse(m) * m` against the identity, and that
+    // transposing does not change the determinant.
+    macro_rules! inverse_test {
+        ($test:ident: $($name:ident($comps:expr)),*) => {
+            #[test]
+            fn $test() {
+                $({
+                    let m = $name($comps);
+                    let identity = $name((1.0,));
+
+                    for product in [m * m.inverse(), m.inverse() * m] {
+                        let diff = (product - identity).components();
+
+                        assert!(diff.into_iter().flatten().all(|comp| comp.abs() < 1e-5), "{product:?}");
+                    }
+                    assert_eq!(m.transpose().determinant(), m.determinant());
+                })*
+            }
+        };
+    }
+
+    inverse_test!(
+        mat_inverse:
+        mat3((2.0, 1.0, 0.0, 0.0, 3.0, 1.0, 1.0, 0.0, 4.0)),
+        mat4((4.0, 1.0, 0.0, 2.0, 0.0, 3.0, 1.0, 0.0, 1.0, 0.0, 2.0, 1.0, 0.0, 2.0, 0.0, 5.0))
+    );
+    inverse_test!(
+        dmat_inverse:
+        dmat3((2.0, 1.0, 0.0, 0.0, 3.0, 1.0, 1.0, 0.0, 4.0)),
+        dmat4((4.0, 1.0, 0.0, 2.0, 0.0, 3.0, 1.0, 0.0, 1.0, 0.0, 2.0, 1.0, 0.0, 2.0, 0.0, 5.0))
+    );
+
+    #[test]
+    fn try_inverse_rejects_overflow() {
+        let tiny = mat2((1e-20, 0.0, 0.0, 1e-20));
+
+        assert_ne!(tiny.determinant(), 0.0);
+        assert_eq!(tiny.try_inverse(), None);
+        assert_eq!(
+            mat3((1.0, 2.0, 3.0, 2.0, 4.0, 6.0, 0.0, 0.0, 1.0)).try_inverse(),
+            None
+        );
+        assert_eq!(mat4((0.0,)).try_inverse(), None);
+        assert_eq!(
+            dmat2((1e-20, 0.0, 0.0, 1e-20)).try_inverse(),
+            Some(dmat2((1e20, 0.0, 0.0, 1e20)))
+        );
+        assert_eq!(mat2((2.0,)).try_inverse(), Some(mat2((0.5,))));
+    }
+
+    #[test]
+    fn mat_arithmetic() {
+        let a = mat3((2.0, 1.0, 0.0, 0.0, 3.0, 1.0, 1.0, 0.0, 4.0));
+        let b = mat3((1.0, 0.0, 2.0, 3.0, 1.0, 0.0, 0.0, 2.0, 1.0));
+
+        assert_eq!(a.determinant(), 25.0);
+        assert_eq!(a * b, mat3((4.0, 1.0, 8.0, 6.0, 6.0, 1.0, 1.0, 6.0, 6.0)));
+        assert_eq!(a * 2.0, mat3((4.0, 2.0, 0.0, 0.0, 6.0, 2.0, 2.0, 0.0, 8.0)));
+        assert_eq!(2.0 * a, a * 2.0);
+        assert_eq!(a / 2.0, a * 0.5);
+        assert_eq!(a + b, mat3((3.0, 1.0, 2.0, 3.0, 4.0, 1.0, 1.0, 2.0, 5.0)));
+        assert_eq!(
+            a - b,
+            mat3((1.0, 1.0, -2.0, -3.0, 2.0, 1.0, 1.0, -2.0, 3.0))
+        );
+
+        let mut c = a;
+        c *= b;
+        c -= a * b;
+        assert_eq!(c, mat3((0.0,)));
+    }
+
+    #[test]
+    fn mat_products() {
+        let a = mat2x3((1.0, 2.0, 3.0, 4.0, 5.0, 6.0));
+        let b = mat3x2((1.0, 0.0, 2.0, 1.0, 0.0, 3.0));
+
+        assert_eq!(
+            a * b,
+            mat3((1.0, 2.0, 3.0, 6.0, 9.0, 12.0, 12.0, 15.0, 18.0))
+        );
+        assert_eq!(b * a, mat2((5.0, 11.0, 14.0, 23.0)));
+        assert_eq!(a * mat2((1.0,)), a);
+        assert_eq!(mat4x2((b,)) * mat2x4((a,)), mat2((5.0, 11.0, 14.0, 23.0)));
+
+        assert_eq!(a.transpose(), mat3x2((1.0, 4.0, 2.0, 5.0, 3.0, 6.0)));
+        assert_eq!(a.transpose().transpose(), a);
+
+        let v = vec3((1.0, 0.0, -1.0));
+
+        assert_eq!(v * a, vec2((-2.0, -2.0)));
+        assert_eq!(v * a, a.transpose() * v);
+        assert_eq!(vec2((1.0, 2.0)) * b, vec3((1.0, 4.0, 6.0)));
+
+        let mut u = vec2((1.0, 2.0));
+        u *= mat2((0.0, 1.0, 1.0, 0.0));
+        assert_eq!(u, vec2((2.0, 1.0)));
+
+        assert_eq!(
+            vec3((1.0, 2.0, 3.0)).outer_product(vec2((4.0, 5.0))),
+            mat2x3((4.0, 8.0, 12.0, 5.0, 10.0, 15.0))
+        );
+    }
+
+    #[test]
+    fn float_ops() {
+        let v = vec2((-1.0, 3.0));
+
+        assert_eq!(v % 2.0, vec2((1.0, 1.0)));
+        // floored and euclidean modulo only differ for a negative divisor
+        assert_eq!(vec2((1.0, -1.0)) % -2.0, vec2((-1.0, -1.0)));
+        assert_eq!(v % vec2((-2.0, 2.0)), vec2((-1.0, 1.0)));
+
+        let mut w = v;
+        w %= 2.0;
+        assert_eq!(w, vec2((1.0, 1.0)));
+
+        assert_eq!(-v, vec2((1.0, -3.0)));
+        assert_eq!(-dvec3((0.0, 1.0, -2.0)), dvec3((-0.0, -1.0, 2.0)));
+        assert_eq!(-ivec2((1, -2)), ivec2((-1, 2)));
+
+        assert_eq!(v + 1.0, vec2((0.0, 4.0)));
+        assert_eq!(v - 1.0, vec2((-2.0, 2.0)));
+        assert_eq!(1.0 + v, v + 1.0);
+        assert_eq!(1.0 - v, vec2((2.0, -2.0)));
+        assert_eq!(2.0 * v, vec2((-2.0, 6.0)));
+        assert_eq!(6.0 / vec2((2.0, -3.0)), vec2((3.0, -2.0)));
+        assert_eq!(10 - ivec2((1, 2)), ivec2((9, 8)));
+    }
+
+    #[test]
+    fn int_ops() {
+        let a = ivec3((12, -5, i32::MAX));
+        let b = ivec3((10, 3, 1));
+
+        assert_eq!(a.checked_add(b), None);
+        assert_eq!(a.wrapping_add(b), ivec3((22, -2, i32::MIN)));
+        assert_eq!(
+            ivec2((1, 2)).checked_add(ivec2((3, 4))),
+            Some(ivec2((4, 6)))
+        );
+        assert_eq!(uvec2((0, 1)).checked_sub(uvec2((1, 0))), None);
+        assert_eq!(
+            uvec2((0, 1)).wrapping_sub(uvec2((1, 0))),
+            uvec2((u32::MAX, 1))
+        );
+
+        assert_eq!(a & b, ivec3((8, 3, 1)));
+        assert_eq!(a | b, ivec3((14, -5, i32::MAX)));
+        assert_eq!(a ^ b, ivec3((6, -8, i32::MAX - 1)));
+        assert_eq!(!ivec2((0, -1)), ivec2((-1, 0)));
+        assert_eq!(a & 4, ivec3((4, 0, 4)));
+
+        assert_eq!(ivec3((1, 2, -8)) << ivec3((3, 1, 0)), ivec3((8, 4, -8)));
+        assert_eq!(ivec3((1, 2, -8)) >> 1, ivec3((0, 1, -4)));
+        assert_eq!(uvec2((0x80, 1)) >> uvec2((7, 0)), uvec2((1, 1)));
+        assert_eq!(ivec2((7, -7)) % 3, ivec2((1, -1)));
+
+        let cells =
+            std::collections::HashMap::from([(ivec3((1, -1, 0)), 'a'), (ivec3((0, 1, -1)), 'b')]);
+
+        assert_eq!(cells[&ivec3((1, -1, 0))], 'a');
+        assert_eq!(cells.get(&ivec3((0, 1, -1))), Some(&'b'));
+        assert_eq!(cells.get(&ivec3((0, 0, 0))), None);
+    }
+
+    access_test!(vec_access: vec2, vec3, vec4 => |i| i as f32 + 0.5);
+    access_test!(dvec_access: dvec2, dvec3, dvec4 => |i| i as f64 + 0.5);
+    access_test!(ivec_access: ivec2, ivec3, ivec4 => |i| i as i32 - 2);
+    access_test!(uvec_access: uvec2, uvec3, uvec4 => |i| i as u32 + 7);
+    access_test!(bvec_access: bvec2, bvec3, bvec4 => |i| i % 2 == 0);
+
+    access_test!(
+        mat_access: mat2, mat3, mat4, mat2x3, mat3x2, mat2x4, mat4x2, mat3x4, mat4x3
+            => |i| core::array::from_fn(|j| (i * 4 + j) as f32)
+    );
+    access_test!(
+        dmat_access: dmat2, dmat3, dmat4, dmat2x3, dmat3x2, dmat2x4, dmat4x2, dmat3x4, dmat4x3
+            => |i| core::array::from_fn(|j| (i * 4 + j) as f64)
+    );
 }
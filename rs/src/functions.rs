@@ -1,34 +1,21 @@
-use core::mem::MaybeUninit;
+#![allow(non_snake_case)]
 
 use crate::constructs::*;
 
 fn trans<const P: usize, const N: usize, T: Components<N>>(
     value: [T; P],
     clos: impl Fn([T::Comp; P]) -> T::Comp,
-) -> T
-where
-    [(); N]: Sized,
-{
+) -> T {
     let compset = value.map(Components::components);
 
-    let mut out = [MaybeUninit::uninit(); N];
-
-    for i in 0..N {
-        out[i].write(clos(compset.map(|comps| comps[i])));
-    }
-
-    // Unsafe: Initialized
-    T::from_components(unsafe { core::mem::transmute_copy(&out) })
+    T::from_components(core::array::from_fn(|i| clos(compset.map(|comps| comps[i]))))
 }
 
 fn reduce<const P: usize, const N: usize, U, T: Components<N>>(
     value: [T; P],
     mut init: U,
     clos: impl Fn([T::Comp; P], &mut U),
-) -> U
-where
-    [(); N]: Sized,
-{
+) -> U {
     let compset = value.map(Components::components);
 
     for i in 0..N {
@@ -38,27 +25,506 @@ where
     init
 }
 
+// Broadcasts a GLSL `genType` argument that may be given either as a scalar or
+// as a full vector, e.g. the bounds of `clamp(vec3, float, float)`.
+fn splat<P: Copy, T: Constructor<(P,)>>(para: P) -> T {
+    T::new((para,))
+}
+
+fn compare<const N: usize, T: Relational<N>>(
+    lhs: T,
+    rhs: T,
+    clos: impl Fn(T::Comp, T::Comp) -> bool,
+) -> T::Bool {
+    let [lhs, rhs] = [lhs, rhs].map(Components::components);
+
+    T::Bool::from_components(core::array::from_fn(|i| clos(lhs[i], rhs[i])))
+}
+
 // binary
 
-pub fn dot<const N: usize, T: Components<N, Comp = f64>>(lhs: T, rhs: T) -> f64
+pub fn dot<const N: usize, T: Components<N>>(lhs: T, rhs: T) -> T::Comp
 where
-    [(); N]: Sized,
+    T::Comp: Float,
 {
-    reduce([lhs, rhs], 0.0, |[lhc, rhc], acc| *acc += lhc * rhc)
+    reduce([lhs, rhs], T::Comp::ZERO, |[lhc, rhc], acc| *acc += lhc * rhc)
 }
 
-pub fn rem<const N: usize, T: Components<N, Comp = f64>>(lhs: T, rhs: T) -> T
+// GLSL `mod`: floored, so the result takes the sign of `rhs`. This used to be
+// `rem_euclid`, which is never negative and differs for negative `rhs`.
+pub fn rem<const N: usize, T: Components<N>>(lhs: T, rhs: T) -> T
 where
-    [(); N]: Sized,
+    T::Comp: Float,
 {
-    trans([lhs, rhs], |[lhc, rhc]| lhc.rem_euclid(rhc))
+    trans([lhs, rhs], |[lhc, rhc]| lhc - rhc * (lhc / rhc).floor())
 }
 
 // unary
 
-pub fn sqrt<const N: usize, T: Components<N, Comp = f64>>(value: T) -> T
+pub fn sqrt<const N: usize, T: Components<N>>(value: T) -> T
 where
-    [(); N]: Sized,
+    T::Comp: Float,
 {
     trans([value], |[comp]| comp.sqrt())
 }
+
+macro_rules! component_wise {
+    ($($name:ident($($para:ident),*) => $value:expr;)*) => {
+        $(
+            pub fn $name<const N: usize, T: Components<N>>($($para: T),*) -> T
+            where
+                T::Comp: Float,
+            {
+                trans([$($para),*], |[$($para),*]| $value)
+            }
+        )*
+    };
+}
+
+// angle and trigonometry
+
+component_wise! {
+    radians(degrees) => degrees.to_radians();
+    degrees(radians) => radians.to_degrees();
+
+    sin(angle) => angle.sin();
+    cos(angle) => angle.cos();
+    tan(angle) => angle.tan();
+    asin(x) => x.asin();
+    acos(x) => x.acos();
+    atan(y_over_x) => y_over_x.atan();
+    atan2(y, x) => y.atan2(x);
+
+    sinh(x) => x.sinh();
+    cosh(x) => x.cosh();
+    tanh(x) => x.tanh();
+    asinh(x) => x.asinh();
+    acosh(x) => x.acosh();
+    atanh(x) => x.atanh();
+}
+
+// exponential
+
+component_wise! {
+    pow(x, y) => x.powf(y);
+    exp(x) => x.exp();
+    log(x) => x.ln();
+    exp2(x) => x.exp2();
+    log2(x) => x.log2();
+    inversesqrt(x) => T::Comp::ONE / x.sqrt();
+}
+
+// common
+
+component_wise! {
+    abs(x) => x.abs();
+    sign(x) => {
+        if x > T::Comp::ZERO {
+            T::Comp::ONE
+        } else if x < T::Comp::ZERO {
+            -T::Comp::ONE
+        } else {
+            T::Comp::ZERO
+        }
+    };
+    floor(x) => x.floor();
+    ceil(x) => x.ceil();
+    fract(x) => x - x.floor();
+}
+
+pub fn min<const N: usize, P: Copy, T: Components<N> + Constructor<(P,)>>(x: T, y: P) -> T
+where
+    T::Comp: Float,
+{
+    trans([x, splat(y)], |[x, y]| if y < x { y } else { x })
+}
+
+pub fn max<const N: usize, P: Copy, T: Components<N> + Constructor<(P,)>>(x: T, y: P) -> T
+where
+    T::Comp: Float,
+{
+    trans([x, splat(y)], |[x, y]| if x < y { y } else { x })
+}
+
+pub fn clamp<const N: usize, P: Copy, T: Components<N> + Constructor<(P,)>>(
+    x: T,
+    min_val: P,
+    max_val: P,
+) -> T
+where
+    T::Comp: Float,
+{
+    min(max(x, min_val), max_val)
+}
+
+pub fn mix<const N: usize, P: Copy, T: Components<N> + Constructor<(P,)>>(x: T, y: T, a: P) -> T
+where
+    T::Comp: Float,
+{
+    trans([x, y, splat(a)], |[x, y, a]| x * (T::Comp::ONE - a) + y * a)
+}
+
+pub fn step<const N: usize, P: Copy, T: Components<N> + Constructor<(P,)>>(edge: P, x: T) -> T
+where
+    T::Comp: Float,
+{
+    trans([splat(edge), x], |[edge, x]| {
+        if x < edge {
+            T::Comp::ZERO
+        } else {
+            T::Comp::ONE
+        }
+    })
+}
+
+pub fn smoothstep<const N: usize, P: Copy, T: Components<N> + Constructor<(P,)>>(
+    edge0: P,
+    edge1: P,
+    x: T,
+) -> T
+where
+    T::Comp: Float,
+{
+    let two = T::Comp::ONE + T::Comp::ONE;
+    let three = two + T::Comp::ONE;
+
+    trans([splat(edge0), splat(edge1), x], |[edge0, edge1, x]| {
+        let t = (x - edge0) / (edge1 - edge0);
+        let t = if t < T::Comp::ZERO {
+            T::Comp::ZERO
+        } else if t > T::Comp::ONE {
+            T::Comp::ONE
+        } else {
+            t
+        };
+
+        t * t * (three - two * t)
+    })
+}
+
+// geometric
+
+pub fn length<const N: usize, T: Components<N>>(x: T) -> T::Comp
+where
+    T::Comp: Float,
+{
+    dot(x, x).sqrt()
+}
+
+pub fn distance<const N: usize, T: Components<N>>(p0: T, p1: T) -> T::Comp
+where
+    T::Comp: Float,
+{
+    length(trans([p0, p1], |[p0, p1]| p0 - p1))
+}
+
+// Like GLSL, a zero-length `x` is not special-cased and yields NaN components.
+pub fn normalize<const N: usize, T: Components<N>>(x: T) -> T
+where
+    T::Comp: Float,
+{
+    let len = length(x);
+
+    trans([x], |[x]| x / len)
+}
+
+pub fn cross<T: Components<3>>(x: T, y: T) -> T
+where
+    T::Comp: Float,
+{
+    let [x0, x1, x2] = x.components();
+    let [y0, y1, y2] = y.components();
+
+    T::from_components([x1 * y2 - y1 * x2, x2 * y0 - y2 * x0, x0 * y1 - y0 * x1])
+}
+
+pub fn faceforward<const N: usize, T: Components<N>>(n: T, i: T, nref: T) -> T
+where
+    T::Comp: Float,
+{
+    if dot(nref, i) < T::Comp::ZERO {
+        n
+    } else {
+        trans([n], |[n]| -n)
+    }
+}
+
+pub fn reflect<const N: usize, T: Components<N>>(i: T, n: T) -> T
+where
+    T::Comp: Float,
+{
+    let d = dot(n, i);
+
+    trans([i, n], |[i, n]| i - (d + d) * n)
+}
+
+pub fn refract<const N: usize, T: Components<N>>(i: T, n: T, eta: T::Comp) -> T
+where
+    T::Comp: Float,
+{
+    let d = dot(n, i);
+    let k = T::Comp::ONE - eta * eta * (T::Comp::ONE - d * d);
+
+    if k < T::Comp::ZERO {
+        trans([i], |_| T::Comp::ZERO)
+    } else {
+        let s = eta * d + k.sqrt();
+
+        trans([i, n], |[i, n]| eta * i - s * n)
+    }
+}
+
+// vector relational
+
+pub fn lessThan<const N: usize, T: Relational<N>>(x: T, y: T) -> T::Bool
+where
+    T::Comp: PartialOrd,
+{
+    compare(x, y, |x, y| x < y)
+}
+
+pub fn lessThanEqual<const N: usize, T: Relational<N>>(x: T, y: T) -> T::Bool
+where
+    T::Comp: PartialOrd,
+{
+    compare(x, y, |x, y| x <= y)
+}
+
+pub fn greaterThan<const N: usize, T: Relational<N>>(x: T, y: T) -> T::Bool
+where
+    T::Comp: PartialOrd,
+{
+    compare(x, y, |x, y| x > y)
+}
+
+pub fn greaterThanEqual<const N: usize, T: Relational<N>>(x: T, y: T) -> T::Bool
+where
+    T::Comp: PartialOrd,
+{
+    compare(x, y, |x, y| x >= y)
+}
+
+pub fn equal<const N: usize, T: Relational<N>>(x: T, y: T) -> T::Bool
+where
+    T::Comp: PartialEq,
+{
+    compare(x, y, |x, y| x == y)
+}
+
+pub fn notEqual<const N: usize, T: Relational<N>>(x: T, y: T) -> T::Bool
+where
+    T::Comp: PartialEq,
+{
+    compare(x, y, |x, y| x != y)
+}
+
+pub fn any<const N: usize, T: Components<N, Comp = bool>>(x: T) -> bool {
+    reduce([x], false, |[x], acc| *acc |= x)
+}
+
+pub fn all<const N: usize, T: Components<N, Comp = bool>>(x: T) -> bool {
+    reduce([x], true, |[x], acc| *acc &= x)
+}
+
+pub fn not<const N: usize, T: Components<N, Comp = bool>>(x: T) -> T {
+    trans([x], |[x]| !x)
+}
+
+// Component-wise `a ? y : x`, the GLSL ES 3.00 `mix(genType, genType, genBType)` overload.
+pub fn select<const N: usize, T: Relational<N>>(x: T, y: T, a: T::Bool) -> T {
+    let [x, y] = [x, y].map(Components::components);
+    let a = a.components();
+
+    T::from_components(core::array::from_fn(|i| if a[i] { y[i] } else { x[i] }))
+}
+
+// matrix
+
+pub fn matrixCompMult<const N: usize, const M: usize, C: Float, T: Components<N, Comp = [C; M]>>(
+    x: T,
+    y: T,
+) -> T {
+    trans([x, y], |[x, y]| core::array::from_fn(|i| x[i] * y[i]))
+}
+
+pub fn outerProduct<C: OuterProduct<R>, R>(c: C, r: R) -> C::Output {
+    c.outer_product(r)
+}
+
+pub fn transpose<M: Transpose>(m: M) -> M::Output {
+    m.transpose()
+}
+
+pub fn determinant<M: Inverse>(m: M) -> M::Scalar {
+    m.determinant()
+}
+
+pub fn inverse<M: Inverse>(m: M) -> M {
+    m.inverse()
+}
+
+pub fn try_inverse<M: Inverse>(m: M) -> Option<M> {
+    m.try_inverse()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn min_max_clamp() {
+        let v = vec3((1.0, -2.0, 5.0));
+
+        assert_eq!(min(v, 0.0), vec3((0.0, -2.0, 0.0)));
+        assert_eq!(max(v, 0.0), vec3((1.0, 0.0, 5.0)));
+        assert_eq!(min(vec2((1.0, 4.0)), vec2((3.0, 2.0))), vec2((1.0, 2.0)));
+        assert_eq!(max(vec2((1.0, 4.0)), vec2((3.0, 2.0))), vec2((3.0, 4.0)));
+        assert_eq!(
+            clamp(vec3((-0.5, 0.25, 1.5)), 0.0, 1.0),
+            vec3((0.0, 0.25, 1.0))
+        );
+        assert_eq!(
+            clamp(vec2((-3.0, 3.0)), vec2((-1.0, 0.0)), vec2((1.0, 2.0))),
+            vec2((-1.0, 2.0))
+        );
+        assert_eq!(clamp(7.0, 0.0, 5.0), 5.0);
+    }
+
+    #[test]
+    fn mix_step_smoothstep() {
+        assert_eq!(
+            mix(vec2((0.0, 10.0)), vec2((4.0, 20.0)), 0.25),
+            vec2((1.0, 12.5))
+        );
+        assert_eq!(
+            mix(vec2((0.0, 10.0)), vec2((4.0, 20.0)), vec2((0.0, 1.0))),
+            vec2((0.0, 20.0))
+        );
+
+        // `x == edge` is not below the edge
+        assert_eq!(step(0.5, vec3((0.4, 0.5, 0.6))), vec3((0.0, 1.0, 1.0)));
+        assert_eq!(step(vec2((1.0, 2.0)), vec2((1.5, 1.5))), vec2((1.0, 0.0)));
+
+        assert_eq!(
+            smoothstep(0.0, 2.0, vec4((-1.0, 0.0, 0.5, 1.0))),
+            vec4((0.0, 0.0, 0.15625, 0.5))
+        );
+        assert_eq!(smoothstep(0.0, 2.0, vec3((2.0, 3.0, 1e9))), vec3((1.0,)));
+    }
+
+    #[test]
+    fn rem_is_floored() {
+        let x = vec2((5.5, -5.5));
+        let y = vec2((-2.0, 2.0));
+
+        assert_eq!(rem(x, y), vec2((-0.5, 0.5)));
+        assert_eq!(rem(x, y), x % y);
+        assert_eq!(5.5f32.rem_euclid(-2.0), 1.5);
+    }
+
+    #[test]
+    fn relational() {
+        let x = vec3((1.0, 2.0, 3.0));
+        let y = vec3((2.0, 2.0, 2.0));
+
+        assert_eq!(lessThan(x, y), bvec3((true, false, false)));
+        assert_eq!(lessThanEqual(x, y), bvec3((true, true, false)));
+        assert_eq!(greaterThan(x, y), bvec3((false, false, true)));
+        assert_eq!(greaterThanEqual(x, y), bvec3((false, true, true)));
+        assert_eq!(equal(ivec2((1, 2)), ivec2((1, 3))), bvec2((true, false)));
+        assert_eq!(notEqual(ivec2((1, 2)), ivec2((1, 3))), bvec2((false, true)));
+        assert_eq!(
+            equal(bvec2((true, false)), bvec2((true, true))),
+            bvec2((true, false))
+        );
+
+        assert!(any(bvec3((false, true, false))));
+        assert!(!any(bvec2((false,))));
+        assert!(all(bvec4((true,))));
+        assert!(!all(bvec3((true, false, true))));
+        assert_eq!(not(bvec2((true, false))), bvec2((false, true)));
+
+        let a = lessThan(x, y);
+        assert_eq!(select(x, y, a), vec3((2.0, 2.0, 3.0)));
+        assert_eq!(
+            select(uvec2((1, 2)), uvec2((3, 4)), bvec2((false, true))),
+            uvec2((1, 4))
+        );
+    }
+
+    #[test]
+    fn matrix_functions() {
+        let m = mat3((2.0, 1.0, 0.0, 0.0, 3.0, 1.0, 1.0, 0.0, 4.0));
+
+        assert_eq!(
+            transpose(m),
+            mat3((2.0, 0.0, 1.0, 1.0, 3.0, 0.0, 0.0, 1.0, 4.0))
+        );
+        assert_eq!(
+            transpose(mat2x3((1.0, 2.0, 3.0, 4.0, 5.0, 6.0))),
+            mat3x2((1.0, 4.0, 2.0, 5.0, 3.0, 6.0))
+        );
+        assert_eq!(determinant(m), 25.0);
+        assert_eq!(determinant(dmat2((1.0, 2.0, 3.0, 4.0))), -2.0);
+        assert_eq!(
+            inverse(mat2((2.0, 0.0, 0.0, 4.0))),
+            mat2((0.5, 0.0, 0.0, 0.25))
+        );
+        assert_eq!(try_inverse(m), Some(inverse(m)));
+        assert_eq!(try_inverse(mat2((1.0, 2.0, 2.0, 4.0))), None);
+        assert_eq!(try_inverse(mat2((1e-20, 0.0, 0.0, 1e-20))), None);
+    }
+
+    #[test]
+    fn normalize_zero_is_nan() {
+        let n = normalize(vec3((0.0,)));
+
+        assert!(n.x.is_nan() && n.y.is_nan() && n.z.is_nan());
+        assert_eq!(normalize(vec2((3.0, 4.0))), vec2((0.6, 0.8)));
+    }
+
+    #[test]
+    fn cross_basis() {
+        let [x, y, z] = [
+            vec3((1.0, 0.0, 0.0)),
+            vec3((0.0, 1.0, 0.0)),
+            vec3((0.0, 0.0, 1.0)),
+        ];
+
+        assert_eq!(cross(x, y), z);
+        assert_eq!(cross(y, z), x);
+        assert_eq!(cross(z, x), y);
+        assert_eq!(cross(y, x), -z);
+        assert_eq!(cross(x, x), vec3((0.0,)));
+    }
+
+    #[test]
+    fn faceforward_sign() {
+        let n = vec3((0.0, 0.0, 1.0));
+        let i = vec3((0.0, 0.0, -1.0));
+
+        assert_eq!(faceforward(n, i, n), n);
+        assert_eq!(faceforward(n, -i, n), -n);
+        // `dot(nref, i) == 0` is not negative, so `n` gets flipped
+        assert_eq!(faceforward(n, vec3((1.0, 0.0, 0.0)), n), -n);
+    }
+
+    #[test]
+    fn reflect_mirrors() {
+        let i = normalize(vec2((1.0, -1.0)));
+
+        assert_eq!(reflect(i, vec2((0.0, 1.0))), normalize(vec2((1.0, 1.0))));
+    }
+
+    #[test]
+    fn refract_total_internal_reflection() {
+        let n = vec2((0.0, 1.0));
+        let grazing = normalize(vec2((1.0, -0.2)));
+
+        // Leaving glass for air at a shallow angle makes `k` negative
+        assert_eq!(refract(grazing, n, 1.5), vec2((0.0,)));
+        // Head on, the ray passes straight through whatever `eta` is
+        assert_eq!(refract(vec2((0.0, -1.0)), n, 1.5), vec2((0.0, -1.0)));
+        assert!(distance(refract(grazing, n, 1.0), grazing) < 1e-6);
+    }
+}
@@ -0,0 +1,1189 @@
+use core::ops::*;
+
+use crate::constructs::*;
+
+// Axial coordinates, `s` being implied as `-q - r`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Axial {
+    pub q: i32,
+    pub r: i32,
+}
+
+impl Axial {
+    pub fn new(q: i32, r: i32) -> Axial {
+        Axial { q, r }
+    }
+
+    pub fn s(self) -> i32 {
+        -self.q - self.r
+    }
+}
+
+// Cube coordinates. The fields are private to uphold `q + r + s == 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Cube {
+    q: i32,
+    r: i32,
+    s: i32,
+}
+
+impl Cube {
+    pub fn new(q: i32, r: i32, s: i32) -> Option<Cube> {
+        (q + r + s == 0).then_some(Cube { q, r, s })
+    }
+
+    pub fn q(self) -> i32 {
+        self.q
+    }
+
+    pub fn r(self) -> i32 {
+        self.r
+    }
+
+    pub fn s(self) -> i32 {
+        self.s
+    }
+}
+
+impl From<Axial> for Cube {
+    fn from(Axial { q, r }: Axial) -> Cube {
+        Cube { q, r, s: -q - r }
+    }
+}
+
+impl From<Cube> for Axial {
+    fn from(Cube { q, r, .. }: Cube) -> Axial {
+        Axial { q, r }
+    }
+}
+
+impl From<ivec2> for Axial {
+    fn from(ivec2 { x, y }: ivec2) -> Axial {
+        Axial { q: x, r: y }
+    }
+}
+
+impl From<Axial> for ivec2 {
+    fn from(Axial { q, r }: Axial) -> ivec2 {
+        ivec2((q, r))
+    }
+}
+
+impl From<Cube> for ivec3 {
+    fn from(Cube { q, r, s }: Cube) -> ivec3 {
+        ivec3((q, r, s))
+    }
+}
+
+// Which rows (`*R`, pointy top) or columns (`*Q`, flat top) are shoved by half a cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Offset {
+    OddR,
+    EvenR,
+    OddQ,
+    EvenQ,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct OffsetCoord {
+    pub col: i32,
+    pub row: i32,
+}
+
+impl OffsetCoord {
+    pub fn new(col: i32, row: i32) -> OffsetCoord {
+        OffsetCoord { col, row }
+    }
+
+    pub fn from_axial(Axial { q, r }: Axial, offset: Offset) -> OffsetCoord {
+        // `n & 1` is the parity for negative `n` as well, keeping the halving exact
+        match offset {
+            Offset::OddR => OffsetCoord::new(q + (r - (r & 1)) / 2, r),
+            Offset::EvenR => OffsetCoord::new(q + (r + (r & 1)) / 2, r),
+            Offset::OddQ => OffsetCoord::new(q, r + (q - (q & 1)) / 2),
+            Offset::EvenQ => OffsetCoord::new(q, r + (q + (q & 1)) / 2),
+        }
+    }
+
+    pub fn to_axial(self, offset: Offset) -> Axial {
+        let OffsetCoord { col, row } = self;
+
+        match offset {
+            Offset::OddR => Axial::new(col - (row - (row & 1)) / 2, row),
+            Offset::EvenR => Axial::new(col - (row + (row & 1)) / 2, row),
+            Offset::OddQ => Axial::new(col, row - (col - (col & 1)) / 2),
+            Offset::EvenQ => Axial::new(col, row - (col + (col & 1)) / 2),
+        }
+    }
+}
+
+// Doubled width suits pointy top layouts, doubled height flat top ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Doubling {
+    Width,
+    Height,
+}
+
+// Doubled coordinates. Only cells with `col + row` even exist, so the fields are
+// private like `Cube`'s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Doubled {
+    col: i32,
+    row: i32,
+}
+
+impl Doubled {
+    pub fn new(col: i32, row: i32) -> Option<Doubled> {
+        ((col + row) & 1 == 0).then_some(Doubled { col, row })
+    }
+
+    pub fn col(self) -> i32 {
+        self.col
+    }
+
+    pub fn row(self) -> i32 {
+        self.row
+    }
+
+    pub fn from_axial(Axial { q, r }: Axial, doubling: Doubling) -> Doubled {
+        match doubling {
+            Doubling::Width => Doubled {
+                col: 2 * q + r,
+                row: r,
+            },
+            Doubling::Height => Doubled {
+                col: q,
+                row: 2 * r + q,
+            },
+        }
+    }
+
+    pub fn to_axial(self, doubling: Doubling) -> Axial {
+        let Doubled { col, row } = self;
+
+        match doubling {
+            Doubling::Width => Axial::new((col - row) / 2, row),
+            Doubling::Height => Axial::new(col, (row - col) / 2),
+        }
+    }
+}
+
+// Fractional cube coordinates, as given by `Layout::pixel_to_hex`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct FractionalHex {
+    pub q: f32,
+    pub r: f32,
+    pub s: f32,
+}
+
+impl FractionalHex {
+    pub fn new(q: f32, r: f32) -> FractionalHex {
+        FractionalHex { q, r, s: -q - r }
+    }
+
+    // Rounds every component, then recomputes the one that moved the furthest from
+    // the other two so `q + r + s == 0` holds again.
+    pub fn round(self) -> Cube {
+        let (q, r, s) = (self.q.round(), self.r.round(), self.s.round());
+        let (dq, dr, ds) = ((q - self.q).abs(), (r - self.r).abs(), (s - self.s).abs());
+        let (q, r, s) = (q as i32, r as i32, s as i32);
+
+        if dq > dr && dq > ds {
+            Cube { q: -r - s, r, s }
+        } else if dr > ds {
+            Cube { q, r: -q - s, s }
+        } else {
+            Cube { q, r, s: -q - r }
+        }
+    }
+}
+
+impl From<Cube> for FractionalHex {
+    fn from(Cube { q, r, s }: Cube) -> FractionalHex {
+        FractionalHex {
+            q: q as f32,
+            r: r as f32,
+            s: s as f32,
+        }
+    }
+}
+
+impl From<Axial> for FractionalHex {
+    fn from(axial: Axial) -> FractionalHex {
+        Cube::from(axial).into()
+    }
+}
+
+impl From<vec2> for FractionalHex {
+    fn from(vec2 { x, y }: vec2) -> FractionalHex {
+        FractionalHex::new(x, y)
+    }
+}
+
+impl From<FractionalHex> for vec2 {
+    fn from(FractionalHex { q, r, .. }: FractionalHex) -> vec2 {
+        vec2((q, r))
+    }
+}
+
+macro_rules! hex_ops {
+    ($name:ident { $($field:ident),+ }) => {
+        impl Add for $name {
+            type Output = $name;
+
+            fn add(self, rhs: $name) -> $name {
+                $name { $($field: self.$field + rhs.$field),+ }
+            }
+        }
+
+        impl Sub for $name {
+            type Output = $name;
+
+            fn sub(self, rhs: $name) -> $name {
+                $name { $($field: self.$field - rhs.$field),+ }
+            }
+        }
+
+        impl Mul<i32> for $name {
+            type Output = $name;
+
+            fn mul(self, rhs: i32) -> $name {
+                $name { $($field: self.$field * rhs),+ }
+            }
+        }
+
+        impl Neg for $name {
+            type Output = $name;
+
+            fn neg(self) -> $name {
+                $name { $($field: -self.$field),+ }
+            }
+        }
+
+        impl AddAssign for $name {
+            fn add_assign(&mut self, rhs: $name) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl SubAssign for $name {
+            fn sub_assign(&mut self, rhs: $name) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl MulAssign<i32> for $name {
+            fn mul_assign(&mut self, rhs: i32) {
+                *self = *self * rhs;
+            }
+        }
+    };
+}
+
+hex_ops!(Axial { q, r });
+hex_ops!(Cube { q, r, s });
+
+// Axial `(q, r)` offsets, in counter-clockwise order for a y-down layout.
+const DIRECTIONS: [(i32, i32); 6] = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)];
+const DIAGONALS: [(i32, i32); 6] = [(2, -1), (1, -2), (-1, -1), (-2, 1), (-1, 2), (1, 1)];
+
+// Names for the `direction` indices with a `Layout` whose y axis points down, like
+// the shader's flipped `pos`. A negative `Layout::size.y` swaps north and south.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointyDirection {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlatDirection {
+    SouthEast,
+    NorthEast,
+    North,
+    NorthWest,
+    SouthWest,
+    South,
+}
+
+// Diagonal `i` lies between directions `i` and `i + 1`, so it has its own names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointyDiagonal {
+    NorthEast,
+    North,
+    NorthWest,
+    SouthWest,
+    South,
+    SouthEast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FlatDiagonal {
+    East,
+    NorthEast,
+    NorthWest,
+    West,
+    SouthWest,
+    SouthEast,
+}
+
+// A diagonal index, which the edge direction names deliberately don't convert to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Diagonal(pub usize);
+
+impl From<usize> for Diagonal {
+    fn from(index: usize) -> Diagonal {
+        Diagonal(index)
+    }
+}
+
+impl From<PointyDiagonal> for Diagonal {
+    fn from(diagonal: PointyDiagonal) -> Diagonal {
+        Diagonal(diagonal as usize)
+    }
+}
+
+impl From<FlatDiagonal> for Diagonal {
+    fn from(diagonal: FlatDiagonal) -> Diagonal {
+        Diagonal(diagonal as usize)
+    }
+}
+
+impl From<PointyDirection> for usize {
+    fn from(direction: PointyDirection) -> usize {
+        direction as usize
+    }
+}
+
+impl From<FlatDirection> for usize {
+    fn from(direction: FlatDirection) -> usize {
+        direction as usize
+    }
+}
+
+impl Cube {
+    pub fn direction(direction: impl Into<usize>) -> Cube {
+        let (q, r) = DIRECTIONS[direction.into() % 6];
+
+        Axial::new(q, r).into()
+    }
+
+    pub fn diagonal(diagonal: impl Into<Diagonal>) -> Cube {
+        let (q, r) = DIAGONALS[diagonal.into().0 % 6];
+
+        Axial::new(q, r).into()
+    }
+
+    // Distance to the origin, i.e. the shader's `len`.
+    pub fn length(self) -> i32 {
+        (self.q.abs() + self.r.abs() + self.s.abs()) / 2
+    }
+
+    pub fn distance(self, other: Cube) -> i32 {
+        (self - other).length()
+    }
+
+    pub fn neighbor(self, direction: impl Into<usize>) -> Cube {
+        self + Cube::direction(direction)
+    }
+
+    pub fn diagonal_neighbor(self, diagonal: impl Into<Diagonal>) -> Cube {
+        self + Cube::diagonal(diagonal)
+    }
+
+    pub fn neighbors(self) -> [Cube; 6] {
+        core::array::from_fn(|i| self.neighbor(i))
+    }
+
+    pub fn diagonals(self) -> [Cube; 6] {
+        core::array::from_fn(|i| self.diagonal_neighbor(i))
+    }
+}
+
+impl Axial {
+    pub fn direction(direction: impl Into<usize>) -> Axial {
+        Cube::direction(direction).into()
+    }
+
+    pub fn diagonal(diagonal: impl Into<Diagonal>) -> Axial {
+        Cube::diagonal(diagonal).into()
+    }
+
+    pub fn length(self) -> i32 {
+        Cube::from(self).length()
+    }
+
+    pub fn distance(self, other: Axial) -> i32 {
+        (self - other).length()
+    }
+
+    pub fn neighbor(self, direction: impl Into<usize>) -> Axial {
+        self + Axial::direction(direction)
+    }
+
+    pub fn diagonal_neighbor(self, diagonal: impl Into<Diagonal>) -> Axial {
+        self + Axial::diagonal(diagonal)
+    }
+
+    pub fn neighbors(self) -> [Axial; 6] {
+        core::array::from_fn(|i| self.neighbor(i))
+    }
+
+    pub fn diagonals(self) -> [Axial; 6] {
+        core::array::from_fn(|i| self.diagonal_neighbor(i))
+    }
+}
+
+impl Cube {
+    pub fn range(self, radius: i32) -> Range {
+        let Cube { q, r, s } = self;
+
+        Range::new(
+            ivec3((q - radius, r - radius, s - radius)),
+            ivec3((q + radius, r + radius, s + radius)),
+        )
+    }
+
+    pub fn ring(self, radius: i32) -> Ring {
+        Ring {
+            hex: self + Cube::direction(4usize) * radius,
+            radius,
+            // A ring of radius zero is just `self`, so only the last side is walked
+            side: match radius {
+                ..0 => 6,
+                0 => 5,
+                _ => 0,
+            },
+            step: 0,
+        }
+    }
+
+    // The rings from zero to `radius`, innermost first.
+    pub fn spiral(self, radius: i32) -> Spiral {
+        Spiral {
+            center: self,
+            radius,
+            ring: (radius >= 0).then(|| self.ring(0)),
+        }
+    }
+}
+
+// All hexes with each of `q`, `r` and `s` within the inclusive `min`/`max` bounds,
+// in `q` then `r` order.
+#[derive(Debug, Clone)]
+pub struct Range {
+    min: ivec3,
+    max: ivec3,
+    q: i32,
+    r: i32,
+}
+
+impl Range {
+    fn new(min: ivec3, max: ivec3) -> Range {
+        Range {
+            min,
+            max,
+            q: min.x,
+            r: min.y.max(-min.x - max.z),
+        }
+    }
+
+    fn row(&self, q: i32) -> (i32, i32) {
+        (
+            self.min.y.max(-q - self.max.z),
+            self.max.y.min(-q - self.min.z),
+        )
+    }
+
+    // The hexes in both ranges, restarting from the beginning.
+    pub fn intersection(&self, other: &Range) -> Range {
+        let (a, b) = (self, other);
+
+        Range::new(
+            ivec3((
+                a.min.x.max(b.min.x),
+                a.min.y.max(b.min.y),
+                a.min.z.max(b.min.z),
+            )),
+            ivec3((
+                a.max.x.min(b.max.x),
+                a.max.y.min(b.max.y),
+                a.max.z.min(b.max.z),
+            )),
+        )
+    }
+}
+
+impl Iterator for Range {
+    type Item = Cube;
+
+    fn next(&mut self) -> Option<Cube> {
+        while self.q <= self.max.x {
+            if self.r <= self.row(self.q).1 {
+                let (q, r) = (self.q, self.r);
+                self.r += 1;
+
+                return Some(Cube { q, r, s: -q - r });
+            }
+
+            self.q += 1;
+            self.r = self.row(self.q).0;
+        }
+
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.q..=self.max.x)
+            .map(|q| {
+                let (lo, hi) = self.row(q);
+                let lo = if q == self.q { self.r } else { lo };
+
+                (hi - lo + 1).max(0) as usize
+            })
+            .sum();
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Range {}
+impl core::iter::FusedIterator for Range {}
+
+#[derive(Debug, Clone)]
+pub struct Ring {
+    hex: Cube,
+    radius: i32,
+    side: usize,
+    step: i32,
+}
+
+impl Iterator for Ring {
+    type Item = Cube;
+
+    fn next(&mut self) -> Option<Cube> {
+        if self.side >= 6 {
+            return None;
+        }
+
+        let hex = self.hex;
+        self.hex = hex.neighbor(self.side);
+        self.step += 1;
+
+        if self.step >= self.radius {
+            self.step = 0;
+            self.side += 1;
+        }
+
+        Some(hex)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = match self.radius {
+            0 => 6 - self.side,
+            radius => (6 - self.side.min(6)) * radius.max(0) as usize - self.step as usize,
+        };
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Ring {}
+impl core::iter::FusedIterator for Ring {}
+
+#[derive(Debug, Clone)]
+pub struct Spiral {
+    center: Cube,
+    radius: i32,
+    ring: Option<Ring>,
+}
+
+impl Iterator for Spiral {
+    type Item = Cube;
+
+    fn next(&mut self) -> Option<Cube> {
+        let ring = self.ring.as_mut()?;
+
+        if let Some(hex) = ring.next() {
+            return Some(hex);
+        }
+
+        if ring.radius < self.radius {
+            *ring = self.center.ring(ring.radius + 1);
+            ring.next()
+        } else {
+            self.ring = None;
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.ring.as_ref().map_or(0, |ring| {
+            ring.len()
+                + (ring.radius + 1..=self.radius)
+                    .map(|n| 6 * n as usize)
+                    .sum::<usize>()
+        });
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Spiral {}
+impl core::iter::FusedIterator for Spiral {}
+
+// Shifts samples off the edges and corners they'd otherwise hit exactly so every
+// tie resolves the same way. Sums to zero to stay on the `q + r + s == 0` plane.
+const NUDGE: FractionalHex = FractionalHex {
+    q: 1e-4,
+    r: 2e-4,
+    s: -3e-4,
+};
+
+impl Cube {
+    // One hex per step from `self` to `other`, both included, each adjacent to the last.
+    pub fn line(self, other: Cube) -> Line {
+        Line {
+            from: self,
+            delta: other - self,
+            steps: self.distance(other),
+            step: 0,
+        }
+    }
+
+    // Every hex the segment between the centers of `self` and `other` touches,
+    // including those it only grazes at a corner, in the order it reaches them.
+    pub fn supercover(self, other: Cube) -> Supercover {
+        Supercover {
+            from: self,
+            delta: other - self,
+            hex: Some(self),
+            grazed: None,
+        }
+    }
+}
+
+impl FractionalHex {
+    pub fn lerp(self, other: FractionalHex, t: f32) -> FractionalHex {
+        FractionalHex {
+            q: self.q + (other.q - self.q) * t,
+            r: self.r + (other.r - self.r) * t,
+            s: self.s + (other.s - self.s) * t,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Line {
+    from: Cube,
+    delta: Cube,
+    steps: i32,
+    step: i32,
+}
+
+impl Iterator for Line {
+    type Item = Cube;
+
+    fn next(&mut self) -> Option<Cube> {
+        if self.step > self.steps {
+            return None;
+        }
+
+        // Interpolating relative to `from` makes precision depend only on the
+        // length of the line, not on how far it is from the origin
+        let t = self.step as f32 / self.steps.max(1) as f32;
+        let hex = FractionalHex::default().lerp(self.delta.into(), t);
+        self.step += 1;
+
+        let hex = FractionalHex {
+            q: hex.q + NUDGE.q,
+            r: hex.r + NUDGE.r,
+            s: hex.s + NUDGE.s,
+        };
+
+        Some(self.from + hex.round())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.steps + 1 - self.step) as usize;
+
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Line {}
+impl core::iter::FusedIterator for Line {}
+
+// Walks the segment `from + delta * t` cell by cell. With `u = p - hex` for a point
+// `p`, a cell spans `|u.q - u.r| <= 1`, `|u.r - u.s| <= 1` and `|u.s - u.q| <= 1`,
+// each bound being the edge shared with one neighbor. Those differences are linear
+// in `t` with integral coefficients, so the crossings are compared exactly.
+//
+// Walking in cube space gives the cells the drawn segment touches: every `Layout`,
+// and the euclidean basis in main.rs, maps cube space linearly onto the plane, which
+// takes the segment to the drawn segment and each cell to its drawn hexagon. The
+// main.rs basis only names the cells differently, its axial `(q, r)` being the cube
+// with axial `(q - r, r)`.
+#[derive(Debug, Clone)]
+pub struct Supercover {
+    from: Cube,
+    delta: Cube,
+    hex: Option<Cube>,
+    grazed: Option<Cube>,
+}
+
+impl Supercover {
+    // The time the segment leaves `hex` as a fraction, and the cells beyond the one
+    // or two (through a corner) edges it crosses then.
+    fn exit(&self, hex: Cube) -> Option<((i64, i64), Cube, Option<Cube>)> {
+        let u = self.from - hex;
+        let e = [u.q - u.r, u.r - u.s, u.s - u.q];
+        let f = [
+            self.delta.q - self.delta.r,
+            self.delta.r - self.delta.s,
+            self.delta.s - self.delta.q,
+        ];
+
+        let mut exit: Option<((i64, i64), Cube, Option<Cube>)> = None;
+
+        for k in (0..3).filter(|&k| f[k] != 0) {
+            let sign = f[k].signum();
+            let t = (((sign - e[k]) * sign) as i64, (f[k] * sign) as i64);
+
+            let mut d = [0; 3];
+            (d[k], d[(k + 1) % 3]) = (sign, -sign);
+            let neighbor = hex
+                + Cube {
+                    q: d[0],
+                    r: d[1],
+                    s: d[2],
+                };
+
+            exit = match exit {
+                Some((first, edge, _)) if first.0 * t.1 == t.0 * first.1 => {
+                    Some((first, edge, Some(neighbor)))
+                }
+                Some((first, ..)) if first.0 * t.1 < t.0 * first.1 => exit,
+                _ => Some((t, neighbor, None)),
+            };
+        }
+
+        // Past the end of the segment, which lies inside its last cell
+        exit.filter(|&((num, den), ..)| num < den)
+    }
+}
+
+impl Iterator for Supercover {
+    type Item = Cube;
+
+    fn next(&mut self) -> Option<Cube> {
+        if let Some(hex) = self.grazed.take() {
+            return Some(hex);
+        }
+
+        let hex = self.hex.take()?;
+
+        self.hex = self.exit(hex).map(|(t, a, b)| match b {
+            None => a,
+            // Leaving through a corner touches both cells beyond it, continue in the
+            // one the segment stays in for a while
+            Some(b) => {
+                let stays = |hex| {
+                    self.exit(hex)
+                        .is_none_or(|(next, ..)| next.0 * t.1 > t.0 * next.1)
+                };
+                let (next, grazed) = if stays(a) { (a, b) } else { (b, a) };
+                self.grazed = Some(grazed);
+
+                next
+            }
+        });
+
+        Some(hex)
+    }
+}
+
+impl core::iter::FusedIterator for Supercover {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    Q,
+    R,
+    S,
+}
+
+// Rotations are by 60° steps, left being counter-clockwise with the y axis pointing
+// down like `PointyDirection` and `FlatDirection` assume.
+impl Cube {
+    pub fn rotate_left(self) -> Cube {
+        Cube {
+            q: -self.s,
+            r: -self.q,
+            s: -self.r,
+        }
+    }
+
+    pub fn rotate_right(self) -> Cube {
+        Cube {
+            q: -self.r,
+            r: -self.s,
+            s: -self.q,
+        }
+    }
+
+    // Rotates `steps` times to the left, or to the right when negative.
+    pub fn rotate(self, steps: i32) -> Cube {
+        match steps.rem_euclid(6) {
+            0 => self,
+            1 => self.rotate_left(),
+            2 => self.rotate_left().rotate_left(),
+            3 => -self,
+            4 => self.rotate_right().rotate_right(),
+            _ => self.rotate_right(),
+        }
+    }
+
+    // Mirrors across the line through the origin the axis stays constant along.
+    pub fn reflect(self, axis: Axis) -> Cube {
+        let Cube { q, r, s } = self;
+
+        match axis {
+            Axis::Q => Cube { q, r: s, s: r },
+            Axis::R => Cube { q: s, r, s: q },
+            Axis::S => Cube { q: r, r: q, s },
+        }
+    }
+
+    pub fn reflect_q(self) -> Cube {
+        self.reflect(Axis::Q)
+    }
+
+    pub fn reflect_r(self) -> Cube {
+        self.reflect(Axis::R)
+    }
+
+    pub fn reflect_s(self) -> Cube {
+        self.reflect(Axis::S)
+    }
+
+    pub fn rotate_left_around(self, center: Cube) -> Cube {
+        center + (self - center).rotate_left()
+    }
+
+    pub fn rotate_right_around(self, center: Cube) -> Cube {
+        center + (self - center).rotate_right()
+    }
+
+    pub fn rotate_around(self, center: Cube, steps: i32) -> Cube {
+        center + (self - center).rotate(steps)
+    }
+
+    pub fn reflect_around(self, center: Cube, axis: Axis) -> Cube {
+        center + (self - center).reflect(axis)
+    }
+}
+
+// Reflects (if any), then rotates about `center`, then translates by `offset`, so
+// a shape defined around `center` can be stamped anywhere in any of its 12 poses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Transform {
+    pub center: Cube,
+    pub reflection: Option<Axis>,
+    pub rotation: i32,
+    pub offset: Cube,
+}
+
+impl Transform {
+    pub fn new(center: Cube) -> Transform {
+        Transform {
+            center,
+            ..Transform::default()
+        }
+    }
+
+    pub fn apply(self, hex: Cube) -> Cube {
+        let hex = match self.reflection {
+            Some(axis) => hex.reflect_around(self.center, axis),
+            None => hex,
+        };
+
+        hex.rotate_around(self.center, self.rotation) + self.offset
+    }
+
+    pub fn apply_all(self, hexes: impl IntoIterator<Item = Cube>) -> impl Iterator<Item = Cube> {
+        hexes.into_iter().map(move |hex| self.apply(hex))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::*;
+    use std::collections::HashSet;
+
+    const OFFSETS: [Offset; 4] = [Offset::OddR, Offset::EvenR, Offset::OddQ, Offset::EvenQ];
+    const DOUBLINGS: [Doubling; 2] = [Doubling::Width, Doubling::Height];
+
+    #[test]
+    fn conversions_round_trip() {
+        for q in -20..=20 {
+            for r in -20..=20 {
+                let axial = Axial::new(q, r);
+                let cube = Cube::from(axial);
+
+                assert_eq!(Axial::from(cube), axial);
+                assert_eq!(Cube::new(q, r, -q - r), Some(cube));
+                assert_eq!(Axial::from(ivec2::from(axial)), axial);
+
+                for offset in OFFSETS {
+                    assert_eq!(OffsetCoord::from_axial(axial, offset).to_axial(offset), axial);
+
+                    // Every offset coordinate is a cell too
+                    let coord = OffsetCoord::new(q, r);
+                    assert_eq!(OffsetCoord::from_axial(coord.to_axial(offset), offset), coord);
+                }
+
+                for doubling in DOUBLINGS {
+                    let doubled = Doubled::from_axial(axial, doubling);
+
+                    assert_eq!(Doubled::new(doubled.col(), doubled.row()), Some(doubled));
+                    assert_eq!(doubled.to_axial(doubling), axial);
+                }
+            }
+        }
+    }
+
+    // Compass bearing in degrees, counter-clockwise from east with y pointing down.
+    fn bearing(layout: Layout, hex: Cube) -> f32 {
+        let pos = layout.hex_to_pixel(hex);
+
+        (-pos.y).atan2(pos.x).to_degrees().rem_euclid(360.0)
+    }
+
+    #[test]
+    fn direction_names() {
+        let pointy = Layout::new(Orientation::Pointy, vec2((1.0,)), vec2((0.0,)));
+        let flat = Layout::new(Orientation::Flat, vec2((1.0,)), vec2((0.0,)));
+
+        use {FlatDiagonal as FD, FlatDirection as F, PointyDiagonal as PD, PointyDirection as P};
+
+        let pointy_directions = [(P::East, 0.0), (P::NorthEast, 60.0), (P::NorthWest, 120.0)];
+        let pointy_diagonals = [(PD::NorthEast, 30.0), (PD::North, 90.0), (PD::South, 270.0)];
+        let flat_directions = [(F::NorthEast, 30.0), (F::North, 90.0), (F::SouthEast, 330.0)];
+        let flat_diagonals = [(FD::East, 0.0), (FD::West, 180.0), (FD::SouthEast, 300.0)];
+
+        for (name, angle) in pointy_directions {
+            assert!((bearing(pointy, Cube::direction(name)) - angle).abs() < 1e-3);
+        }
+        for (name, angle) in pointy_diagonals {
+            assert!((bearing(pointy, Cube::diagonal(name)) - angle).abs() < 1e-3);
+        }
+        for (name, angle) in flat_directions {
+            assert!((bearing(flat, Cube::direction(name)) - angle).abs() < 1e-3);
+        }
+        for (name, angle) in flat_diagonals {
+            assert!((bearing(flat, Cube::diagonal(name)) - angle).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn neighbors_and_diagonals() {
+        let center = Cube::from(Axial::new(2, -5));
+
+        assert!(center.neighbors().iter().all(|&hex| hex.distance(center) == 1));
+        assert!(center.diagonals().iter().all(|&hex| hex.distance(center) == 2));
+
+        for i in 0..6 {
+            assert_eq!(Cube::diagonal(i), Cube::direction(i) + Cube::direction(i + 1));
+        }
+
+        assert_eq!(Axial::new(3, -1).distance(Axial::new(-2, 4)), 5);
+    }
+
+    #[test]
+    fn range_ring_spiral_counts() {
+        let center = Cube::from(Axial::new(3, -2));
+
+        for n in 0..8 {
+            let cells = (3 * n * (n + 1) + 1) as usize;
+            let ring = if n == 0 { 1 } else { 6 * n as usize };
+
+            assert_eq!(center.range(n).len(), cells);
+            assert_eq!(center.range(n).count(), cells);
+            assert_eq!(center.spiral(n).len(), cells);
+            assert_eq!(center.spiral(n).count(), cells);
+            assert_eq!(center.ring(n).len(), ring);
+            assert_eq!(center.ring(n).count(), ring);
+
+            assert!(center.range(n).all(|hex| hex.distance(center) <= n));
+            assert!(center.ring(n).all(|hex| hex.distance(center) == n));
+
+            let range: HashSet<_> = center.range(n).collect();
+            assert_eq!(center.spiral(n).collect::<HashSet<_>>(), range);
+        }
+
+        assert_eq!(center.range(-1).count(), 0);
+        assert_eq!(center.ring(-1).count(), 0);
+        assert_eq!(center.spiral(-1).count(), 0);
+    }
+
+    #[test]
+    fn len_counts_down() {
+        let center = Cube::default();
+        let mut spiral = center.spiral(3);
+        let mut range = center.range(3);
+
+        for left in (0..37).rev() {
+            assert!(spiral.next().is_some() && range.next().is_some());
+            assert_eq!((spiral.len(), range.len()), (left, left));
+        }
+    }
+
+    #[test]
+    fn range_intersection() {
+        let a = Cube::from(Axial::new(3, -2));
+
+        for n in 0..5 {
+            for m in 0..5 {
+                for offset in [Axial::new(0, 0), Axial::new(4, -1), Axial::new(-9, 2), Axial::new(2, 3)] {
+                    let b = a + offset.into();
+                    let both = a.range(n).intersection(&b.range(m));
+
+                    let filtered: HashSet<_> = a.range(n).filter(|hex| hex.distance(b) <= m).collect();
+                    assert_eq!(both.len(), filtered.len());
+                    assert_eq!(both.collect::<HashSet<_>>(), filtered);
+                }
+            }
+        }
+    }
+
+    // The part of `a` to `b` within the closed cell `hex`, from its cube bounds.
+    fn overlap(a: Cube, b: Cube, hex: Cube) -> Option<(f64, f64)> {
+        let (u, d) = (a - hex, b - a);
+        let mut t = (0.0f64, 1.0f64);
+
+        for (e, f) in [(u.q - u.r, d.q - d.r), (u.r - u.s, d.r - d.s), (u.s - u.q, d.s - d.q)] {
+            let (e, f) = (e as f64, f as f64);
+
+            if f == 0.0 {
+                if e.abs() > 1.0 {
+                    return None;
+                }
+            } else {
+                let (t0, t1) = ((-1.0 - e) / f, (1.0 - e) / f);
+                t = (t.0.max(t0.min(t1)), t.1.min(t0.max(t1)));
+            }
+        }
+
+        (t.0 <= t.1).then_some(t)
+    }
+
+    #[test]
+    fn line_steps() {
+        let base = Cube::from(Axial::new(137, -420));
+
+        for a in base.range(1) {
+            for b in base.range(8) {
+                let line: Vec<_> = a.line(b).collect();
+
+                assert_eq!(line.len() as i32, a.distance(b) + 1);
+                assert_eq!((line[0], line[line.len() - 1]), (a, b));
+                assert!(line.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+                assert!(line.iter().all(|&hex| overlap(a, b, hex).is_some()));
+            }
+        }
+    }
+
+    #[test]
+    fn supercover_touches() {
+        let base = Cube::from(Axial::new(137, -420));
+
+        for a in base.range(1) {
+            for b in base.range(8) {
+                let cover: Vec<_> = a.supercover(b).collect();
+                let unique: HashSet<_> = cover.iter().copied().collect();
+
+                assert_eq!(unique.len(), cover.len());
+                assert!(cover.windows(2).all(|pair| pair[0].distance(pair[1]) == 1));
+
+                // Every cell a sample along the segment lands in
+                let (from, to) = (FractionalHex::from(a), FractionalHex::from(b));
+                assert!((0..=256).all(|i| unique.contains(&from.lerp(to, i as f32 / 256.0).round())));
+
+                // And exactly the cells the segment touches, corners included
+                let touched: HashSet<_> = a
+                    .range(a.distance(b) + 1)
+                    .filter(|&hex| overlap(a, b, hex).is_some())
+                    .collect();
+                assert_eq!(unique, touched);
+            }
+        }
+    }
+
+    #[test]
+    fn rotations_and_reflections() {
+        let center = Cube::from(Axial::new(-3, 5));
+
+        for i in 0..6usize {
+            assert_eq!(Cube::direction(i).rotate_left(), Cube::direction(i + 1));
+            assert_eq!(Cube::direction(i + 1).rotate_right(), Cube::direction(i));
+        }
+
+        for hex in Cube::default().range(4) {
+            let dist = hex.distance(center);
+
+            assert_eq!(hex.rotate(6), hex);
+            assert_eq!(hex.rotate(-1), hex.rotate_right());
+            assert_eq!(hex.rotate(3), -hex);
+
+            for n in -7..=7 {
+                assert_eq!(hex.rotate(n).rotate(-n), hex);
+                assert_eq!(hex.rotate_around(center, n).distance(center), dist);
+            }
+
+            for axis in [Axis::Q, Axis::R, Axis::S] {
+                assert_eq!(hex.reflect(axis).reflect(axis), hex);
+                assert_eq!(hex.reflect(axis).length(), hex.length());
+                assert_eq!(hex.reflect_around(center, axis).distance(center), dist);
+            }
+
+            assert_eq!(hex.rotate_left_around(center).distance(center), dist);
+            assert_eq!(hex.rotate_right_around(center).distance(center), dist);
+            assert_eq!(hex.reflect_q().q(), hex.q());
+            assert_eq!(hex.reflect_r().r(), hex.r());
+            assert_eq!(hex.reflect_s().s(), hex.s());
+        }
+    }
+
+    #[test]
+    fn transform_stamps_shape() {
+        let center = Cube::from(Axial::new(2, -1));
+        let corner = center.neighbor(0usize);
+        let shape = [center, corner, corner.neighbor(1usize)];
+
+        let transform = Transform {
+            reflection: Some(Axis::Q),
+            rotation: 1,
+            offset: Axial::new(0, 3).into(),
+            ..Transform::new(center)
+        };
+
+        let stamped: Vec<_> = transform.apply_all(shape).collect();
+        let expected = [Axial::new(2, 2), Axial::new(2, 1), Axial::new(3, 0)].map(Cube::from);
+
+        assert_eq!(stamped, expected);
+
+        let identity: Vec<_> = Transform::new(center).apply_all(shape).collect();
+        assert_eq!(identity, shape);
+    }
+
+    #[test]
+    fn invariants_are_checked() {
+        assert_eq!(Cube::new(1, 1, 1), None);
+        assert_eq!(Doubled::new(1, 2), None);
+        assert_eq!(Doubled::new(-1, 3).map(|d| (d.col(), d.row())), Some((-1, 3)));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::constructs::*;
+use crate::functions::*;
+use crate::hex::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Pointy,
+    Flat,
+}
+
+impl Orientation {
+    // Maps axial `(q, r)` to pixels for cells with a circumradius of one.
+    pub fn basis(self) -> mat2 {
+        match self {
+            Orientation::Pointy => mat2((sqrt(3.0), 0.0, sqrt(3.0) / 2.0, 3.0 / 2.0)),
+            Orientation::Flat => mat2((3.0 / 2.0, sqrt(3.0) / 2.0, 0.0, sqrt(3.0))),
+        }
+    }
+
+    pub fn inverse_basis(self) -> mat2 {
+        inverse(self.basis())
+    }
+}
+
+// `size` is the circumradius of a cell along each axis, which need not be equal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Layout {
+    pub orientation: Orientation,
+    pub size: vec2,
+    pub origin: vec2,
+}
+
+impl Layout {
+    pub fn new(orientation: Orientation, size: vec2, origin: vec2) -> Layout {
+        Layout {
+            orientation,
+            size,
+            origin,
+        }
+    }
+
+    // The pixel displacement matching a displacement in hex coordinates.
+    fn offset(self, hex: impl Into<FractionalHex>) -> vec2 {
+        self.orientation.basis() * vec2::from(hex.into()) * self.size
+    }
+
+    pub fn hex_to_pixel(self, hex: impl Into<FractionalHex>) -> vec2 {
+        self.offset(hex) + self.origin
+    }
+
+    pub fn pixel_to_hex(self, pos: vec2) -> FractionalHex {
+        (self.orientation.inverse_basis() * ((pos - self.origin) / self.size)).into()
+    }
+
+    // Corner `i` is shared with the neighbors in directions `i - 1` and `i`, so edge
+    // `i` runs from corner `i` to corner `i + 1` and faces direction `i`.
+    pub fn corners(self, hex: impl Into<FractionalHex>) -> [vec2; 6] {
+        self.inset(hex, 0.0)
+    }
+
+    pub fn edge_midpoints(self, hex: impl Into<FractionalHex>) -> [vec2; 6] {
+        let center = self.hex_to_pixel(hex);
+
+        core::array::from_fn(|i| center + self.offset(Cube::direction(i)) / 2.0)
+    }
+
+    pub fn edges(self, hex: impl Into<FractionalHex>) -> [[vec2; 2]; 6] {
+        let corners = self.corners(hex);
+
+        core::array::from_fn(|i| [corners[i], corners[(i + 1) % 6]])
+    }
+
+    // Pulls the corners towards the center by `amount` of the cell, pushing them out
+    // when negative. Matches `border`, which holds outside `inset(hex, threshold)`.
+    pub fn inset(self, hex: impl Into<FractionalHex>, amount: f32) -> [vec2; 6] {
+        let center = self.hex_to_pixel(hex);
+
+        core::array::from_fn(|i| {
+            // Corners are where three cells meet, a third of the way to each neighbor
+            let corner =
+                (self.offset(Cube::direction(i + 5)) + self.offset(Cube::direction(i))) / 3.0;
+
+            center + corner * (1.0 - amount)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edges_are_shared_with_neighbors() {
+        for orientation in [Orientation::Pointy, Orientation::Flat] {
+            let layout = Layout::new(orientation, vec2((2.0, 1.5)), vec2((10.0, -4.0)));
+            let hex = Cube::from(Axial::new(3, -1));
+
+            for i in 0..6 {
+                let [a, b] = layout.edges(hex)[i];
+                let [c, d] = layout.edges(hex.neighbor(i))[(i + 3) % 6];
+
+                assert!(distance(a, d) < 1e-4 && distance(b, c) < 1e-4);
+                assert!(distance((a + b) / 2.0, layout.edge_midpoints(hex)[i]) < 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn pixel_round_trip() {
+        for orientation in [Orientation::Pointy, Orientation::Flat] {
+            let layout = Layout::new(orientation, vec2((3.0, 1.5)), vec2((10.0, -4.0)));
+
+            for hex in Cube::default().range(20) {
+                assert_eq!(layout.pixel_to_hex(layout.hex_to_pixel(hex)).round(), hex);
+            }
+
+            for i in -20..=20 {
+                let hex = FractionalHex::new(i as f32 * 0.37, 1.3 - i as f32 * 0.61);
+                let back = layout.pixel_to_hex(layout.hex_to_pixel(hex));
+
+                assert!(
+                    (back.q - hex.q).abs() < 1e-4 && (back.r - hex.r).abs() < 1e-4,
+                    "{hex:?} -> {back:?}"
+                );
+                assert!((back.s - hex.s).abs() < 1e-4);
+            }
+        }
+    }
+}
@@ -1,11 +1,13 @@
-#![allow(non_camel_case_types, incomplete_features, unused)]
-#![feature(tuple_trait, generic_const_exprs)]
+#![allow(non_camel_case_types, unused)]
 
 mod constructs;
 mod functions;
+mod hex;
+mod layout;
 
 use crate::constructs::*;
 use crate::functions::*;
+use crate::layout::*;
 
 fn hex_id(uv: vec2) -> vec2 {
     let r = vec2((sqrt(3.0), 1.0));
@@ -19,16 +21,45 @@ fn hex_id(uv: vec2) -> vec2 {
     uv - gv
 }
 
+// The cells `hex_id` picks, one unit apart from center to center.
+fn hex_id_layout() -> Layout {
+    Layout::new(Orientation::Flat, vec2((1.0 / sqrt(3.0),)), vec2((0.0,)))
+}
+
+fn hex_rad(uv: vec2) -> f32 {
+    let r = vec2((sqrt(3.0), 1.0));
+    let h = r / 2.0;
+
+    let a = rem(uv, r) - h;
+    let b = rem(uv - h, r) - h;
+
+    let gv = abs(if dot(a, a) < dot(b, b) { a } else { b });
+
+    max(dot(gv, normalize(r)), gv.y) * 2.0
+}
+
+fn border(pos: vec2, threshold: f32) -> bool {
+    hex_rad(pos) > (1.0 - threshold)
+}
+
+fn to_euclidean_basis() -> mat2 {
+    mat2((3.0 / 2.0, sqrt(3.0) / 2.0, -3.0 / 2.0, sqrt(3.0) / 2.0))
+}
+
+fn from_euclidean_basis() -> mat2 {
+    inverse(to_euclidean_basis())
+}
+
 fn from_euclidean(pos: vec2) -> vec2 {
     let compx = sqrt(3.0) * pos.x / 2.0;
     let compy = pos.y / 2.0;
 
     //return vec2(compy + compx, compy + compx);
-    mat2((1.0 / 3.0, -1.0 / 3.0, sqrt(1.0 / 3.0), sqrt(1.0 / 3.0))) * pos
+    from_euclidean_basis() * pos
 }
 
 fn to_euclidean(axial: vec2) -> vec2 {
-    mat2((3.0 / 2.0, sqrt(3.0) / 2.0, -3.0 / 2.0, sqrt(3.0) / 2.0)) * axial
+    to_euclidean_basis() * axial
 }
 
 fn calc(mut uv: vec2) -> vec2 {
@@ -37,10 +68,100 @@ fn calc(mut uv: vec2) -> vec2 {
     uv *= scale;
     //uv.y *= -1.0;
 
-    from_euclidean(hex_id(uv)) / 4.0
+    (from_euclidean_basis() / 4.0) * hex_id(uv)
 }
 
 fn main() {
     dbg!(from_euclidean(to_euclidean(vec2((1.0, 0.0)))));
     //dbg!(to_euclidean(vec2((1.0, 0.0))));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn euclidean_bases_are_inverse() {
+        let product = from_euclidean_basis() * to_euclidean_basis();
+        let identity = mat2((1.0,));
+
+        assert!((0..2).all(|i| distance(product.col(i), identity.col(i)) < 1e-6));
+
+        let axial = vec2((1.0, -2.0));
+        assert!(distance(from_euclidean(to_euclidean(axial)), axial) < 1e-5);
+    }
+
+    #[test]
+    fn inset_matches_border() {
+        let layout = hex_id_layout();
+
+        for hex in hex::Cube::default().range(2) {
+            let center = layout.hex_to_pixel(hex);
+
+            for threshold in [0.0, 0.1, 0.35] {
+                let outline = layout.inset(hex, threshold);
+                let midpoints =
+                    core::array::from_fn::<_, 6, _>(|i| (outline[i] + outline[(i + 1) % 6]) / 2.0);
+
+                for pos in outline.into_iter().chain(midpoints) {
+                    assert!((hex_rad(pos) - (1.0 - threshold)).abs() < 1e-4);
+                    // Past a zero threshold is the neighbor's inside, not a border
+                    assert!(threshold == 0.0 || border(center + (pos - center) * 1.01, threshold));
+                    assert!(!border(center + (pos - center) * 0.99, threshold));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn supercover_matches_euclidean_basis() {
+        let layout = Layout::new(Orientation::Flat, vec2((1.0,)), vec2((0.0,)));
+        let to_axial = |hex: hex::Cube| floor(from_euclidean(layout.hex_to_pixel(hex)) + 0.5);
+        // The cell `hex_id` puts a point in, scaled from its unit spacing to ours
+        let cell = |axial: vec2| {
+            floor(from_euclidean(hex_id(to_euclidean(axial) / sqrt(3.0)) * sqrt(3.0)) + 0.5)
+        };
+
+        let a = hex::Cube::from(hex::Axial::new(1, -2));
+
+        for b in hex::Cube::default().range(5).filter(|&b| b != a) {
+            assert_eq!(to_axial(b), vec2(((b.q() + b.r()) as f32, b.r() as f32)));
+
+            let cover: Vec<_> = a.supercover(b).map(to_axial).collect();
+            let (from, to) = (to_axial(a), to_axial(b));
+
+            for i in 0..=256 {
+                let pos = mix(from, to, i as f32 / 256.0);
+                assert!(cover.contains(&cell(pos)), "{pos:?} of {a:?} -> {b:?}");
+            }
+
+            // Every cell is within a circumradius of the segment
+            let (from, along) = (to_euclidean(from), to_euclidean(to - from));
+            for center in cover.into_iter().map(to_euclidean) {
+                let t = clamp(dot(center - from, along) / dot(along, along), 0.0, 1.0);
+                assert!(distance(center, from + along * t) <= 1.0 + 1e-4);
+            }
+        }
+    }
+
+    #[test]
+    fn rounding_agrees_with_hex_id() {
+        let layout = hex_id_layout();
+
+        for i in -200..200 {
+            for j in -200..200 {
+                let uv = vec2((i as f32, j as f32)) * 0.025;
+
+                let rounded = layout.hex_to_pixel(layout.pixel_to_hex(uv).round());
+                let picked = hex_id(uv);
+
+                // Points exactly on an edge may go either way
+                assert!(
+                    distance(rounded, picked) < 1e-4
+                        || (distance(uv, rounded) - distance(uv, picked)).abs() < 1e-4,
+                    "{uv:?}: {rounded:?} != {picked:?}"
+                );
+            }
+        }
+    }
+}